@@ -0,0 +1,119 @@
+use egui::{Color32, Id, LayerId, Order, RichText, UserAttentionType, ViewportCommand};
+use serde::{Deserialize, Serialize};
+use std::{
+    fs,
+    path::Path,
+    time::{Duration, Instant},
+};
+
+pub const APP_TITLE: &str = "Rust Clock";
+const FLASH_DURATION: Duration = Duration::from_millis(1500);
+
+/// Alerts that work without sound: a flash over the window, a taskbar attention request
+/// and the countdown in the window title.
+#[derive(Serialize, Deserialize)]
+pub struct Alerts {
+    pub flash: bool,
+    pub attention: bool,
+    pub title: bool,
+    #[serde(skip)]
+    flashing: Option<(Color32, Instant)>,
+    #[serde(skip)]
+    last_title: String, // what the window title was last set to
+}
+
+impl Alerts {
+    pub fn new() -> Self {
+        Self {
+            flash: true,
+            attention: true,
+            title: true,
+            flashing: None,
+            last_title: APP_TITLE.to_string(),
+        }
+    }
+
+    /// Signals a transition in `color`, the colour of the phase that just began.
+    pub fn alert(&mut self, ctx: &egui::Context, color: Color32) {
+        if self.flash {
+            self.flashing = Some((color, Instant::now()));
+        }
+        let focused = ctx.input(|input| input.viewport().focused.unwrap_or(true));
+        if self.attention && !focused {
+            ctx.send_viewport_cmd(ViewportCommand::RequestUserAttention(
+                UserAttentionType::Informational,
+            ));
+        }
+    }
+
+    /// Puts `status` in the window title, or the plain app name when there is none.
+    pub fn set_title(&mut self, ctx: &egui::Context, status: Option<String>) {
+        let title = match status {
+            Some(status) if self.title => status,
+            _ => APP_TITLE.to_string(),
+        };
+        if title != self.last_title {
+            ctx.send_viewport_cmd(ViewportCommand::Title(title.clone()));
+            self.last_title = title;
+        }
+    }
+
+    /// Draws the flash over everything, pulsing twice and fading out.
+    pub fn paint(&mut self, ctx: &egui::Context) {
+        let Some((color, started)) = self.flashing else {
+            return;
+        };
+        let t = started.elapsed().as_secs_f32() / FLASH_DURATION.as_secs_f32();
+        if t >= 1.0 {
+            self.flashing = None;
+            return;
+        }
+
+        let pulse = 0.5 - 0.5 * (t * 2.0 * std::f32::consts::TAU).cos(); // 0 → 1 → 0, twice
+        let alpha = 0.35 * pulse * (1.0 - t);
+        ctx.layer_painter(LayerId::new(Order::Foreground, Id::new("phase_flash")))
+            .rect_filled(ctx.screen_rect(), 0.0, color.gamma_multiply(alpha));
+    }
+
+    pub fn settings_ui(&mut self, ui: &mut egui::Ui) {
+        ui.heading("Visual alerts");
+        ui.add_space(5.0);
+        let mut changed = false;
+        changed |= ui
+            .checkbox(&mut self.flash, "Flash the window when a phase ends")
+            .changed();
+        changed |= ui
+            .checkbox(&mut self.attention, "Ask for attention in the taskbar")
+            .changed();
+        changed |= ui
+            .checkbox(&mut self.title, "Show the countdown in the window title")
+            .changed();
+        ui.label(RichText::new("e.g. \"12:04 · Work · Write docs\"").weak());
+        if changed {
+            self.save();
+        }
+    }
+
+    pub fn save(&self) {
+        if let Some(home_dir) = dirs::home_dir() {
+            let backup_dir = home_dir.join(".rust_pomodoro_backup");
+            fs::create_dir_all(&backup_dir).unwrap_or(());
+            let alerts_json = serde_json::to_string(&self).unwrap_or_default();
+            fs::write(backup_dir.join("alerts.json"), alerts_json).unwrap_or(());
+        }
+    }
+
+    pub fn load() -> Self {
+        if let Some(home_dir) = dirs::home_dir() {
+            let file_path = home_dir.join(".rust_pomodoro_backup/alerts.json");
+            if Path::new(&file_path).exists() {
+                if let Ok(contents) = fs::read_to_string(file_path) {
+                    if let Ok(alerts) = serde_json::from_str(&contents) {
+                        return alerts;
+                    }
+                }
+            }
+        }
+        Self::new()
+    }
+}
@@ -0,0 +1,126 @@
+// Background sounds for work phases. Everything but the user's file is generated on the fly.
+use rodio::Source;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+const SAMPLE_RATE: u32 = 44_100;
+
+#[derive(Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub enum Ambient {
+    #[default]
+    Off,
+    WhiteNoise,
+    PinkNoise,
+    BrownNoise,
+    Ticking,
+    File,
+}
+
+impl Ambient {
+    pub const ALL: [Ambient; 6] = [
+        Ambient::Off,
+        Ambient::WhiteNoise,
+        Ambient::PinkNoise,
+        Ambient::BrownNoise,
+        Ambient::Ticking,
+        Ambient::File,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Ambient::Off => "Off",
+            Ambient::WhiteNoise => "White noise",
+            Ambient::PinkNoise => "Pink noise",
+            Ambient::BrownNoise => "Brown noise",
+            Ambient::Ticking => "Ticking clock",
+            Ambient::File => "Sound file",
+        }
+    }
+}
+
+/// An endless generated sound.
+pub struct Generator {
+    kind: Ambient,
+    seed: u32,
+    filter: [f32; 3], // pink noise poles, or the brown noise level in the first slot
+    position: u64,    // samples played, for the ticking clock
+}
+
+impl Generator {
+    /// The generator for `kind`, or None for the kinds that are not generated.
+    pub fn new(kind: Ambient) -> Option<Self> {
+        match kind {
+            Ambient::Off | Ambient::File => None,
+            _ => Some(Self {
+                kind,
+                seed: 0x9E37_79B9,
+                filter: [0.0; 3],
+                position: 0,
+            }),
+        }
+    }
+
+    /// Uniform noise in -1..1 from a xorshift generator.
+    fn white(&mut self) -> f32 {
+        self.seed ^= self.seed << 13;
+        self.seed ^= self.seed >> 17;
+        self.seed ^= self.seed << 5;
+        self.seed as f32 / u32::MAX as f32 * 2.0 - 1.0
+    }
+
+    /// A short decaying click once a second, alternating pitch like a tick and a tock.
+    fn tick(&self) -> f32 {
+        let second = self.position / SAMPLE_RATE as u64;
+        let t = (self.position % SAMPLE_RATE as u64) as f32 / SAMPLE_RATE as f32;
+        if t > 0.03 {
+            return 0.0;
+        }
+        let frequency = if second % 2 == 0 { 2000.0 } else { 1600.0 };
+        (std::f32::consts::TAU * frequency * t).sin() * (-t * 200.0).exp() * 0.6
+    }
+}
+
+impl Iterator for Generator {
+    type Item = f32;
+
+    fn next(&mut self) -> Option<f32> {
+        let sample = match self.kind {
+            Ambient::WhiteNoise => self.white() * 0.3,
+            Ambient::PinkNoise => {
+                // Paul Kellet's economy pink noise filter
+                let white = self.white();
+                self.filter[0] = 0.99765 * self.filter[0] + white * 0.099_046;
+                self.filter[1] = 0.963 * self.filter[1] + white * 0.296_516_4;
+                self.filter[2] = 0.57 * self.filter[2] + white * 1.052_691_3;
+                (self.filter[0] + self.filter[1] + self.filter[2] + white * 0.1848) * 0.1
+            }
+            Ambient::BrownNoise => {
+                let white = self.white();
+                self.filter[0] = (self.filter[0] + 0.02 * white) / 1.02;
+                self.filter[0] * 3.5 * 0.3
+            }
+            Ambient::Ticking => self.tick(),
+            Ambient::Off | Ambient::File => 0.0,
+        };
+        self.position += 1;
+        Some(sample)
+    }
+}
+
+impl Source for Generator {
+    fn current_frame_len(&self) -> Option<usize> {
+        None
+    }
+
+    fn channels(&self) -> u16 {
+        1
+    }
+
+    fn sample_rate(&self) -> u32 {
+        SAMPLE_RATE
+    }
+
+    fn total_duration(&self) -> Option<Duration> {
+        None
+    }
+}
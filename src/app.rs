@@ -1,9 +1,18 @@
+use crate::alerts::Alerts;
+use crate::history::{Entry, History, Snapshot};
+use crate::music;
+use crate::planner::{self, Planner};
 use crate::pomodoro::{Pomodoro, PomodoroState};
+use crate::reflection::Journal;
+use crate::sound;
+use crate::sound_settings::SoundSettings;
 use crate::stats::Stats;
+use crate::theme::{self, Themes};
 use crate::timer::Timer;
+use crate::typography::Typography;
 use crate::{clock::Clock, task::TaskList};
 use eframe::egui;
-use egui::{Align, Layout, RichText, Ui};
+use egui::{Align, Align2, Key, KeyboardShortcut, Layout, Modifiers, RichText, Ui};
 use std::time::{Duration, Instant};
 
 #[derive(PartialEq)]
@@ -11,6 +20,9 @@ pub enum Tab {
     Pomodoro,
     Clock,
     Timer,
+    Planner,
+    Journal,
+    Settings,
 }
 
 pub struct ClockApp {
@@ -21,10 +33,24 @@ pub struct ClockApp {
     last_update: Instant,
     task_list: TaskList,
     stats: Stats,
+    history: History,
+    planner: Planner,
+    journal: Journal,
+    sound_settings: SoundSettings,
+    alerts: Alerts,
+    themes: Themes,
+    typography: Typography,
 }
 
 impl ClockApp {
-    pub fn new(_cc: &eframe::CreationContext<'_>) -> Self {
+    pub fn new(cc: &eframe::CreationContext<'_>) -> Self {
+        let sound_settings = SoundSettings::load();
+        sound_settings.apply();
+        let mut themes = Themes::load();
+        themes.update(&cc.egui_ctx);
+        let mut typography = Typography::load();
+        typography.apply(&cc.egui_ctx);
+
         Self {
             clock: Clock::new(),
             pomodoro: Pomodoro::new(),
@@ -33,6 +59,13 @@ impl ClockApp {
             last_update: Instant::now(),
             task_list: TaskList::load_from_file(),
             stats: Stats::load(),
+            history: History::new(),
+            planner: Planner::load(),
+            journal: Journal::load(),
+            sound_settings,
+            alerts: Alerts::load(),
+            themes,
+            typography,
         }
     }
 
@@ -41,6 +74,23 @@ impl ClockApp {
             ui.selectable_value(&mut self.current_tab, Tab::Pomodoro, "Pomodoro");
             ui.selectable_value(&mut self.current_tab, Tab::Clock, "Clock");
             ui.selectable_value(&mut self.current_tab, Tab::Timer, "Timer");
+            ui.selectable_value(&mut self.current_tab, Tab::Planner, "Planner");
+            ui.selectable_value(&mut self.current_tab, Tab::Journal, "Journal");
+            ui.selectable_value(&mut self.current_tab, Tab::Settings, "Settings");
+            ui.with_layout(Layout::right_to_left(Align::Center), |ui| {
+                let muted = self.sound_settings.muted;
+                if ui
+                    .selectable_label(muted, if muted { "🔇" } else { "🔊" })
+                    .on_hover_text(if muted {
+                        "Unmute sounds"
+                    } else {
+                        "Mute sounds"
+                    })
+                    .clicked()
+                {
+                    self.sound_settings.toggle_mute();
+                }
+            });
         });
         ui.add_space(10.0);
         ui.separator();
@@ -58,19 +108,128 @@ impl ClockApp {
         });
     }
 
+    /// Captures the current state of whatever `like` refers to, for the opposite stack.
+    fn snapshot_like(&self, like: &Snapshot) -> Snapshot {
+        match like {
+            Snapshot::Tasks(_) => Snapshot::Tasks(self.task_list.snapshot()),
+            Snapshot::Pomodoro(_) => Snapshot::Pomodoro(self.pomodoro.snapshot()),
+            Snapshot::Timer(_) => Snapshot::Timer(self.timer.snapshot()),
+        }
+    }
+
+    fn restore(&mut self, snapshot: Snapshot) {
+        match snapshot {
+            Snapshot::Tasks(tasks) => self.task_list.restore(tasks),
+            Snapshot::Pomodoro(pomodoro) => self.pomodoro.restore(pomodoro),
+            Snapshot::Timer(timer) => self.timer.restore(timer),
+        }
+    }
+
+    fn undo(&mut self) {
+        if let Some(entry) = self.history.pop_undo() {
+            let current = self.snapshot_like(&entry.snapshot);
+            self.restore(entry.snapshot);
+            self.history.push_redo(Entry {
+                label: entry.label,
+                snapshot: current,
+            });
+        }
+    }
+
+    fn redo(&mut self) {
+        if let Some(entry) = self.history.pop_redo() {
+            let current = self.snapshot_like(&entry.snapshot);
+            self.restore(entry.snapshot);
+            self.history.push_undo(Entry {
+                label: entry.label,
+                snapshot: current,
+            });
+        }
+    }
+
+    fn handle_shortcuts(&mut self, ctx: &egui::Context) {
+        // Text fields have their own undo, leave Ctrl+Z to them while they have focus
+        if ctx.memory(|memory| memory.focused().is_some()) {
+            return;
+        }
+
+        let redo = KeyboardShortcut::new(Modifiers::COMMAND | Modifiers::SHIFT, Key::Z);
+        let undo = KeyboardShortcut::new(Modifiers::COMMAND, Key::Z);
+        if ctx.input_mut(|input| input.consume_shortcut(&redo)) {
+            self.redo();
+        } else if ctx.input_mut(|input| input.consume_shortcut(&undo)) {
+            self.undo();
+        }
+    }
+
+    fn render_undo_toast(&mut self, ctx: &egui::Context) {
+        let Some(label) = self.history.toast().map(str::to_string) else {
+            return;
+        };
+
+        egui::Area::new(egui::Id::new("undo_toast"))
+            .anchor(Align2::CENTER_BOTTOM, [0.0, -40.0])
+            .show(ctx, |ui| {
+                egui::Frame::popup(ui.style()).show(ui, |ui| {
+                    ui.horizontal(|ui| {
+                        ui.label(label);
+                        if ui.button("Undo").clicked() {
+                            self.undo();
+                        }
+                        if ui.small_button("✖").clicked() {
+                            self.history.dismiss_toast();
+                        }
+                    });
+                });
+            });
+    }
+
+    /// "12:04 · Work · Write docs" while the pomodoro runs, otherwise the custom timer's.
+    fn title_status(&self) -> Option<String> {
+        if let Some(status) = self.pomodoro.title_status() {
+            return Some(match self.task_list.running_task() {
+                Some((_, task)) if self.pomodoro.phase() == PomodoroState::Work => {
+                    format!("{} · {}", status, task)
+                }
+                _ => status,
+            });
+        }
+        self.timer.title_status()
+    }
+
     fn update_timers(&mut self, ctx: &egui::Context) {
         let now = Instant::now();
         let elapsed = now.duration_since(self.last_update);
         self.last_update = now;
 
         self.pomodoro.update(elapsed, ctx);
+        sound::set_ambient(self.pomodoro.state == PomodoroState::Work);
         self.timer.update(elapsed, ctx);
+        self.task_list.roll_over();
+        self.task_list.check_reminders();
+        if let Some(phase) = self.pomodoro.take_transition() {
+            self.alerts.alert(ctx, phase.color());
+        }
+        if self.timer.take_finished() {
+            self.alerts.alert(ctx, theme::palette().danger);
+        }
+
+        // Taken every frame, so a session that ends with no task running is not credited
+        // to the next task started
+        let work_completed = self.pomodoro.take_work_completed();
+        if work_completed {
+            // Capture the task before the list archives it
+            let task = self.task_list.running_task();
+            self.journal.prompt(self.pomodoro.work_duration, task);
+        }
+        self.task_list
+            .tick(elapsed, &mut self.pomodoro, work_completed);
 
         if self.pomodoro.state == PomodoroState::Work && elapsed > Duration::from_secs(0) {
             self.stats.add_work_time(elapsed);
         }
 
-        if self.pomodoro.metrics.completed_pomodoros > 0 {
+        if work_completed {
             self.stats.add_pomodoro();
             self.stats.save(); // Save stats after update
         }
@@ -83,8 +242,13 @@ impl ClockApp {
 
 impl eframe::App for ClockApp {
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        self.themes.update(ctx);
         self.clock.update();
         self.update_timers(ctx);
+        if self.task_list.take_unrecorded_change() {
+            self.history.clear();
+        }
+        self.handle_shortcuts(ctx);
 
         egui::CentralPanel::default().show(ctx, |ui| {
             self.render_tab_bar(ui);
@@ -95,21 +259,60 @@ impl eframe::App for ClockApp {
                         ui.add_space(40.0);
                         ui.vertical(|ui| {
                             ui.set_width(ui.available_width() * 0.5);
-                            self.pomodoro.ui(ui);
+                            self.pomodoro.ui(ui, &mut self.history);
+                            if self.sound_settings.music
+                                && !self.sound_settings.music_source.is_empty()
+                            {
+                                ui.vertical_centered(music::controls_ui);
+                            }
+                            ui.vertical_centered(|ui| {
+                                ui.collapsing("Forecast", |ui| {
+                                    let items = self.task_list.plan_items();
+                                    planner::forecast_ui(ui, &self.pomodoro, &items);
+                                });
+                            });
                         });
 
                         ui.vertical(|ui| {
                             ui.set_width(ui.available_width());
                             ui.add_space(20.0);
-                            self.task_list.ui(ui, &mut self.pomodoro);
+                            egui::ScrollArea::vertical().show(ui, |ui| {
+                                self.task_list.ui(ui, &mut self.pomodoro, &mut self.history);
+                            });
                         });
                     });
                 }
                 Tab::Clock => self.clock.ui(ui),
-                Tab::Timer => self.timer.ui(ui),
+                Tab::Timer => self.timer.ui(ui, &mut self.history),
+                Tab::Planner => {
+                    let items = self.task_list.plan_items();
+                    self.planner.ui(ui, &self.pomodoro, &items);
+                }
+                Tab::Journal => self.journal.ui(ui),
+                Tab::Settings => {
+                    egui::ScrollArea::vertical().show(ui, |ui| {
+                        self.themes.settings_ui(ui);
+                        ui.add_space(15.0);
+                        self.typography.settings_ui(ui);
+                        ui.add_space(15.0);
+                        self.alerts.settings_ui(ui);
+                        ui.add_space(15.0);
+                        self.sound_settings.ui(ui);
+                    });
+                }
             }
 
             self.render_footer(ui);
         });
+
+        self.task_list.review_ui(ctx, &mut self.history);
+        self.task_list.reminders_ui(ctx);
+        if self.task_list.take_stop_request() {
+            self.pomodoro.stop();
+        }
+        self.journal.prompt_ui(ctx);
+        self.alerts.paint(ctx);
+        self.alerts.set_title(ctx, self.title_status());
+        self.render_undo_toast(ctx);
     }
 }
@@ -0,0 +1,202 @@
+use chrono::{DateTime, Local, NaiveDate};
+use egui::{CollapsingHeader, RichText};
+use serde::{Deserialize, Serialize};
+use std::{fs, path::Path, time::Duration};
+
+use crate::rollover::Rollover;
+use crate::task::Task;
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct ArchivedTask {
+    pub task: Task,
+    pub completed_at: DateTime<Local>,
+    pub time_spent: Duration,
+}
+
+#[derive(PartialEq, Clone, Copy)]
+enum GroupBy {
+    Day,
+    Week,
+}
+
+impl GroupBy {
+    fn key(&self, at: &DateTime<Local>) -> String {
+        match self {
+            GroupBy::Day => at.format("%A, %B %d, %Y").to_string(),
+            GroupBy::Week => at.format("Week %V, %G").to_string(),
+        }
+    }
+}
+
+pub struct Archive {
+    entries: Vec<ArchivedTask>,
+    search: String,
+    group_by: GroupBy,
+}
+
+impl Archive {
+    pub fn new() -> Self {
+        Self {
+            entries: Vec::new(),
+            search: String::new(),
+            group_by: GroupBy::Day,
+        }
+    }
+
+    pub fn push(&mut self, mut task: Task) {
+        task.running = false;
+        task.completed = true;
+        self.entries.push(ArchivedTask {
+            time_spent: task.time_spent,
+            task,
+            completed_at: Local::now(),
+        });
+        self.save();
+    }
+
+    pub fn entries(&self) -> &[ArchivedTask] {
+        &self.entries
+    }
+
+    pub fn set_entries(&mut self, entries: Vec<ArchivedTask>) {
+        self.entries = entries;
+        self.save();
+    }
+
+    /// Tasks finished on the given working day, which starts at the rollover hour.
+    pub fn completed_on(&self, date: NaiveDate, rollover: &Rollover) -> Vec<&ArchivedTask> {
+        self.entries
+            .iter()
+            .filter(|entry| rollover.day_of(entry.completed_at) == date)
+            .collect()
+    }
+
+    pub fn contains(&self, id: u64) -> bool {
+        self.entries.iter().any(|entry| entry.task.id == id)
+    }
+
+    pub fn max_id(&self) -> u64 {
+        self.entries
+            .iter()
+            .map(|entry| entry.task.id)
+            .max()
+            .unwrap_or(0)
+    }
+
+    fn matches(&self, entry: &ArchivedTask) -> bool {
+        let needle = self.search.trim().to_lowercase();
+        needle.is_empty()
+            || entry.task.name.to_lowercase().contains(&needle)
+            || entry.task.project.to_lowercase().contains(&needle)
+    }
+
+    /// Removes an entry so it can go back onto the active list.
+    pub fn take(&mut self, index: usize) -> Task {
+        let task = self.entries.remove(index).task;
+        self.save();
+        task
+    }
+
+    /// Shows the archive grouped by day or week. Returns the entry the user chose to restore.
+    pub fn ui(&mut self, ui: &mut egui::Ui) -> Option<usize> {
+        ui.horizontal(|ui| {
+            ui.add(
+                egui::TextEdit::singleline(&mut self.search)
+                    .hint_text("Search finished tasks")
+                    .desired_width(160.0),
+            );
+            ui.selectable_value(&mut self.group_by, GroupBy::Day, "By day");
+            ui.selectable_value(&mut self.group_by, GroupBy::Week, "By week");
+        });
+
+        // Newest first, grouped by consecutive day/week keys
+        let mut order: Vec<usize> = (0..self.entries.len())
+            .filter(|&i| self.matches(&self.entries[i]))
+            .collect();
+        order.sort_by(|&a, &b| {
+            self.entries[b]
+                .completed_at
+                .cmp(&self.entries[a].completed_at)
+        });
+
+        let mut groups: Vec<(String, Vec<usize>)> = Vec::new();
+        for i in order {
+            let key = self.group_by.key(&self.entries[i].completed_at);
+            match groups.last_mut() {
+                Some((last, items)) if *last == key => items.push(i),
+                _ => groups.push((key, vec![i])),
+            }
+        }
+
+        if groups.is_empty() {
+            ui.label(RichText::new("Nothing here yet").weak());
+        }
+
+        let mut to_restore = None;
+        for (key, items) in &groups {
+            let total: Duration = items.iter().map(|&i| self.entries[i].time_spent).sum();
+            CollapsingHeader::new(format!(
+                "{} — {} tasks, {}",
+                key,
+                items.len(),
+                format_spent(total)
+            ))
+            .id_salt(("archive_group", key))
+            .default_open(true)
+            .show(ui, |ui| {
+                for &i in items {
+                    let entry = &self.entries[i];
+                    ui.horizontal(|ui| {
+                        ui.label(&entry.task.name);
+                        if !entry.task.project.is_empty() {
+                            ui.label(RichText::new(format!("+{}", entry.task.project)).weak());
+                        }
+                        ui.label(format!(
+                            "({} spent, done {})",
+                            format_spent(entry.time_spent),
+                            entry.completed_at.format("%H:%M")
+                        ));
+                        if ui.small_button("Restore").clicked() {
+                            to_restore = Some(i);
+                        }
+                    });
+                }
+            });
+        }
+
+        to_restore
+    }
+
+    pub fn save(&self) {
+        if let Some(home_dir) = dirs::home_dir() {
+            let backup_dir = home_dir.join(".rust_pomodoro_backup");
+            fs::create_dir_all(&backup_dir).unwrap_or(());
+            let archive_json = serde_json::to_string(&self.entries).unwrap_or_default();
+            fs::write(backup_dir.join("finished_tasks.json"), archive_json).unwrap_or(());
+        }
+    }
+
+    pub fn load() -> Self {
+        let mut archive = Self::new();
+        if let Some(home_dir) = dirs::home_dir() {
+            let file_path = home_dir.join(".rust_pomodoro_backup/finished_tasks.json");
+            if Path::new(&file_path).exists() {
+                if let Ok(contents) = fs::read_to_string(file_path) {
+                    if let Ok(entries) = serde_json::from_str(&contents) {
+                        archive.entries = entries;
+                    }
+                }
+            }
+        }
+        archive
+    }
+}
+
+fn format_spent(duration: Duration) -> String {
+    let minutes = duration.as_secs() / 60;
+    if minutes >= 60 {
+        format!("{}h {}m", minutes / 60, minutes % 60)
+    } else {
+        format!("{}m", minutes)
+    }
+}
@@ -0,0 +1,215 @@
+use egui::{Frame, Id, RichText, Stroke};
+use serde::{Deserialize, Serialize};
+use std::{collections::HashMap, fs, path::Path};
+
+use crate::archive::ArchivedTask;
+use crate::task::Task;
+use crate::theme;
+
+#[derive(Clone, PartialEq)]
+pub enum BoardColumn {
+    Todo,
+    InProgress,
+    Custom(String),
+    Done,
+}
+
+impl BoardColumn {
+    fn title(&self) -> &str {
+        match self {
+            BoardColumn::Todo => "Todo",
+            BoardColumn::InProgress => "In progress",
+            BoardColumn::Custom(name) => name,
+            BoardColumn::Done => "Done",
+        }
+    }
+
+    fn of(task: &Task, custom: &[String]) -> BoardColumn {
+        if task.completed {
+            return BoardColumn::Done; // checked off but not archived yet
+        }
+        if task.running {
+            return BoardColumn::InProgress;
+        }
+        match &task.column {
+            Some(name) if custom.contains(name) => BoardColumn::Custom(name.clone()),
+            _ => BoardColumn::Todo,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct Board {
+    pub enabled: bool,    // show the board instead of the list
+    columns: Vec<String>, // user-defined columns between "In progress" and "Done"
+    #[serde(skip)]
+    new_column: String,
+}
+
+impl Board {
+    pub fn new() -> Self {
+        Self {
+            enabled: false,
+            columns: Vec::new(),
+            new_column: String::new(),
+        }
+    }
+
+    fn all_columns(&self) -> Vec<BoardColumn> {
+        let mut columns = vec![BoardColumn::Todo, BoardColumn::InProgress];
+        columns.extend(self.columns.iter().cloned().map(BoardColumn::Custom));
+        columns.push(BoardColumn::Done);
+        columns
+    }
+
+    /// Draws the board. Returns the card that was dropped and the column it landed in.
+    pub fn ui(
+        &mut self,
+        ui: &mut egui::Ui,
+        tasks: &[&Task],
+        blocked: &HashMap<u64, String>,
+        done_today: &[&ArchivedTask],
+    ) -> Option<(u64, BoardColumn)> {
+        let columns = self.all_columns();
+        let mut dropped = None;
+        let mut column_to_remove = None;
+
+        ui.columns(columns.len(), |uis| {
+            for (ui, column) in uis.iter_mut().zip(&columns) {
+                let cards: Vec<&&Task> = tasks
+                    .iter()
+                    .filter(|task| BoardColumn::of(task, &self.columns) == *column)
+                    .collect();
+
+                ui.horizontal(|ui| {
+                    ui.label(RichText::new(column.title()).strong());
+                    let count = if *column == BoardColumn::Done {
+                        done_today.len() + cards.len()
+                    } else {
+                        cards.len()
+                    };
+                    ui.label(RichText::new(count.to_string()).weak());
+                    if let BoardColumn::Custom(name) = column {
+                        if cards.is_empty() && ui.small_button("✖").clicked() {
+                            column_to_remove = Some(name.clone());
+                        }
+                    }
+                });
+
+                let frame = Frame::group(ui.style()).inner_margin(4.0);
+                let (_, payload) = ui.dnd_drop_zone::<u64, ()>(frame, |ui| {
+                    ui.set_min_size(egui::vec2(ui.available_width(), 120.0));
+                    if *column == BoardColumn::Done {
+                        for entry in done_today {
+                            ui.label(RichText::new(&entry.task.name).strikethrough().weak());
+                        }
+                    }
+                    for task in &cards {
+                        card_ui(ui, task, blocked.get(&task.id));
+                    }
+                });
+
+                if let Some(id) = payload {
+                    // Blocked cards cannot be started
+                    if *column != BoardColumn::InProgress || !blocked.contains_key(&id) {
+                        dropped = Some((*id, column.clone()));
+                    }
+                }
+            }
+        });
+
+        if let Some(name) = column_to_remove {
+            self.columns.retain(|column| *column != name);
+            self.save();
+        }
+
+        ui.horizontal(|ui| {
+            ui.add(
+                egui::TextEdit::singleline(&mut self.new_column)
+                    .hint_text("New column")
+                    .desired_width(100.0),
+            );
+            let name = self.new_column.trim().to_string();
+            let taken = ["Todo", "In progress", "Done"].contains(&name.as_str())
+                || self.columns.contains(&name);
+            if ui
+                .add_enabled(!name.is_empty() && !taken, egui::Button::new("Add column"))
+                .clicked()
+            {
+                self.columns.push(name);
+                self.new_column.clear();
+                self.save();
+            }
+        });
+
+        dropped
+    }
+
+    pub fn save(&self) {
+        if let Some(home_dir) = dirs::home_dir() {
+            let backup_dir = home_dir.join(".rust_pomodoro_backup");
+            fs::create_dir_all(&backup_dir).unwrap_or(());
+            let board_json = serde_json::to_string(&self).unwrap_or_default();
+            fs::write(backup_dir.join("board.json"), board_json).unwrap_or(());
+        }
+    }
+
+    pub fn load() -> Self {
+        if let Some(home_dir) = dirs::home_dir() {
+            let file_path = home_dir.join(".rust_pomodoro_backup/board.json");
+            if Path::new(&file_path).exists() {
+                if let Ok(contents) = fs::read_to_string(file_path) {
+                    if let Ok(board) = serde_json::from_str(&contents) {
+                        return board;
+                    }
+                }
+            }
+        }
+        Self::new()
+    }
+}
+
+fn card_ui(ui: &mut egui::Ui, task: &Task, blocker: Option<&String>) {
+    ui.dnd_drag_source(Id::new(("board_card", task.id)), task.id, |ui| {
+        let stroke = if task.running {
+            Stroke::new(1.0, theme::palette().work)
+        } else {
+            ui.visuals().widgets.noninteractive.bg_stroke
+        };
+        Frame::group(ui.style())
+            .stroke(stroke)
+            .fill(ui.visuals().faint_bg_color)
+            .show(ui, |ui| {
+                ui.set_width(ui.available_width());
+                if let Some(blocker) = blocker {
+                    ui.label(RichText::new(&task.name).color(theme::palette().dim));
+                    ui.label(
+                        RichText::new(format!("⛔ blocked by {}", blocker))
+                            .small()
+                            .color(theme::palette().muted),
+                    );
+                } else if task.completed {
+                    ui.label(RichText::new(&task.name).strikethrough());
+                } else {
+                    ui.label(&task.name);
+                }
+                ui.horizontal_wrapped(|ui| {
+                    if !task.project.is_empty() {
+                        ui.label(RichText::new(format!("+{}", task.project)).weak().small());
+                    }
+                    ui.label(
+                        RichText::new(format!(
+                            "{} min, {} 🍅",
+                            task.duration.as_secs() / 60,
+                            task.estimate
+                        ))
+                        .small(),
+                    );
+                    let (done, total) = task.checklist_progress();
+                    if total > 0 {
+                        ui.label(RichText::new(format!("{}/{}", done, total)).small());
+                    }
+                });
+            });
+    });
+}
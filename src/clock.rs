@@ -1,7 +1,10 @@
 use chrono::{DateTime, Local, Timelike};
-use egui::{Color32, FontId, Pos2, RichText, Stroke, Ui, Vec2};
+use egui::{FontId, Pos2, RichText, Stroke, Ui, Vec2};
 use std::f32::consts::PI;
 
+use crate::theme;
+use crate::typography;
+
 pub struct Clock {
     current_time: DateTime<Local>,
 }
@@ -30,13 +33,15 @@ impl Clock {
             ui.add_space(30.0);
 
             // Analog clock
-            let clock_size = Vec2::splat(ui.available_width().min(240.0));
+            let clock_size = Vec2::splat(typography::ring_size(ui));
             let (response, painter) = ui.allocate_painter(clock_size, egui::Sense::hover());
             let center = response.rect.center();
             let radius = response.rect.width() / 2.0 - 10.0;
 
+            let palette = theme::palette();
+
             // Draw clock face
-            painter.circle_stroke(center, radius, Stroke::new(2.0, Color32::GRAY));
+            painter.circle_stroke(center, radius, Stroke::new(2.0, palette.muted));
 
             // Draw hour markers
             for i in 0..12 {
@@ -51,7 +56,7 @@ impl Clock {
                 let end_pos = Pos2::new(center.x + radius * cos_a, center.y + radius * sin_a);
 
                 let thickness = if i % 3 == 0 { 2.0 } else { 1.0 };
-                painter.line_segment([start_pos, end_pos], Stroke::new(thickness, Color32::GRAY));
+                painter.line_segment([start_pos, end_pos], Stroke::new(thickness, palette.muted));
             }
 
             // Get current time components for clock hands
@@ -68,7 +73,7 @@ impl Clock {
                 center.x + hour_hand_length * hour_angle.cos(),
                 center.y + hour_hand_length * hour_angle.sin(),
             );
-            painter.line_segment([center, hour_pos], Stroke::new(3.0, Color32::WHITE));
+            painter.line_segment([center, hour_pos], Stroke::new(3.0, palette.strong));
 
             // Draw minute hand
             let minute_angle = minutes * PI / 30.0 - PI / 2.0;
@@ -77,7 +82,7 @@ impl Clock {
                 center.x + minute_hand_length * minute_angle.cos(),
                 center.y + minute_hand_length * minute_angle.sin(),
             );
-            painter.line_segment([center, minute_pos], Stroke::new(2.0, Color32::WHITE));
+            painter.line_segment([center, minute_pos], Stroke::new(2.0, palette.strong));
 
             // Draw second hand
             let second_angle = seconds * PI / 30.0 - PI / 2.0;
@@ -86,13 +91,10 @@ impl Clock {
                 center.x + second_hand_length * second_angle.cos(),
                 center.y + second_hand_length * second_angle.sin(),
             );
-            painter.line_segment(
-                [center, second_pos],
-                Stroke::new(1.0, Color32::from_rgb(255, 100, 100)),
-            );
+            painter.line_segment([center, second_pos], Stroke::new(1.0, palette.danger));
 
             // Draw center dot
-            painter.circle_filled(center, 4.0, Color32::WHITE);
+            painter.circle_filled(center, 4.0, palette.strong);
         });
     }
 }
@@ -0,0 +1,139 @@
+use chrono::NaiveDate;
+use egui::RichText;
+use serde::{Deserialize, Serialize};
+use std::{fs, path::Path};
+
+use crate::theme;
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct DayRecord {
+    pub date: NaiveDate,
+    pub committed: u32,
+    pub finished: u32,
+}
+
+/// Tasks committed to the To Do Today sheet, per day.
+#[derive(Serialize, Deserialize)]
+pub struct Commitments {
+    day: Option<NaiveDate>,
+    ids: Vec<u64>, // tasks committed to `day`
+    history: Vec<DayRecord>,
+}
+
+impl Commitments {
+    pub fn new() -> Self {
+        Self {
+            day: None,
+            ids: Vec::new(),
+            history: Vec::new(),
+        }
+    }
+
+    pub fn commit(&mut self, id: u64, today: NaiveDate) {
+        self.day.get_or_insert(today);
+        if !self.ids.contains(&id) {
+            self.ids.push(id);
+            self.save();
+        }
+    }
+
+    pub fn withdraw(&mut self, id: u64) {
+        let before = self.ids.len();
+        self.ids.retain(|other| *other != id);
+        if self.ids.len() != before {
+            self.save();
+        }
+    }
+
+    pub fn ids(&self) -> &[u64] {
+        &self.ids
+    }
+
+    /// Puts back today's commitments as an undo left them.
+    pub fn set_ids(&mut self, ids: Vec<u64>) {
+        self.ids = ids;
+        self.save();
+    }
+
+    /// Closes the current day, keeping how many of its commitments still stood and how many
+    /// of those were finished.
+    pub fn close_day(&mut self, committed: u32, finished: u32) {
+        if let Some(date) = self.day.take() {
+            if committed > 0 {
+                self.history.push(DayRecord {
+                    date,
+                    committed,
+                    finished,
+                });
+            }
+            self.ids.clear();
+            self.save();
+        }
+    }
+
+    /// Committed vs finished for the last two weeks, newest first.
+    pub fn history_ui(&self, ui: &mut egui::Ui) {
+        if self.history.is_empty() {
+            ui.label(RichText::new("No finished days yet").weak());
+            return;
+        }
+
+        let recent = &self.history[self.history.len().saturating_sub(14)..];
+        let committed: u32 = recent.iter().map(|day| day.committed).sum();
+        let finished: u32 = recent.iter().map(|day| day.finished).sum();
+        ui.label(format!(
+            "{} of {} committed tasks finished ({:.0}%)",
+            finished,
+            committed,
+            finished as f32 / committed.max(1) as f32 * 100.0
+        ));
+
+        egui::Grid::new("commitment_history")
+            .striped(true)
+            .spacing([20.0, 4.0])
+            .show(ui, |ui| {
+                for day in recent.iter().rev() {
+                    ui.label(day.date.format("%a %d %b").to_string());
+                    ui.label(format!("{}/{}", day.finished, day.committed));
+                    let color = if day.finished >= day.committed {
+                        theme::palette().success
+                    } else {
+                        theme::palette().muted
+                    };
+                    let open = day.committed.saturating_sub(day.finished) as usize;
+                    ui.label(
+                        RichText::new(format!(
+                            "{}{}",
+                            "■".repeat(day.finished as usize),
+                            "□".repeat(open)
+                        ))
+                        .color(color),
+                    );
+                    ui.end_row();
+                }
+            });
+    }
+
+    pub fn save(&self) {
+        if let Some(home_dir) = dirs::home_dir() {
+            let backup_dir = home_dir.join(".rust_pomodoro_backup");
+            fs::create_dir_all(&backup_dir).unwrap_or(());
+            let commitments_json = serde_json::to_string(&self).unwrap_or_default();
+            fs::write(backup_dir.join("commitments.json"), commitments_json).unwrap_or(());
+        }
+    }
+
+    pub fn load() -> Self {
+        if let Some(home_dir) = dirs::home_dir() {
+            let file_path = home_dir.join(".rust_pomodoro_backup/commitments.json");
+            if Path::new(&file_path).exists() {
+                if let Ok(contents) = fs::read_to_string(file_path) {
+                    if let Ok(commitments) = serde_json::from_str(&contents) {
+                        return commitments;
+                    }
+                }
+            }
+        }
+        Self::new()
+    }
+}
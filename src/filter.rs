@@ -0,0 +1,316 @@
+use chrono::{Days, NaiveDate};
+use egui::RichText;
+use serde::{Deserialize, Serialize};
+use std::{fs, path::Path};
+
+use crate::task::{Priority, Task};
+
+#[derive(Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub enum StatusFilter {
+    #[default]
+    All,
+    Incomplete,
+    Completed,
+}
+
+/// Due filters are relative to the current day so saved views like "Today" stay useful.
+#[derive(Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub enum DueFilter {
+    #[default]
+    Any,
+    Overdue,
+    Today,    // due today or earlier
+    ThisWeek, // due within the next 7 days or earlier
+    Before(NaiveDate),
+}
+
+impl DueFilter {
+    fn label(&self) -> String {
+        match self {
+            DueFilter::Any => "Any time".to_string(),
+            DueFilter::Overdue => "Overdue".to_string(),
+            DueFilter::Today => "Due today".to_string(),
+            DueFilter::ThisWeek => "Due this week".to_string(),
+            DueFilter::Before(date) => format!("Before {}", date.format("%d %b")),
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct TaskFilter {
+    pub text: String,
+    pub project: Option<String>,
+    pub tag: Option<String>,
+    pub priority: Option<Priority>,
+    pub due: DueFilter,
+    pub status: StatusFilter,
+}
+
+impl TaskFilter {
+    pub fn matches(&self, task: &Task, today: NaiveDate) -> bool {
+        let text = self.text.trim().to_lowercase();
+        if !text.is_empty()
+            && !task.name.to_lowercase().contains(&text)
+            && !task
+                .checklist
+                .iter()
+                .any(|item| item.text.to_lowercase().contains(&text))
+        {
+            return false;
+        }
+        if self
+            .project
+            .as_ref()
+            .is_some_and(|project| *project != task.project)
+        {
+            return false;
+        }
+        if self
+            .tag
+            .as_ref()
+            .is_some_and(|tag| !task.tags.contains(tag))
+        {
+            return false;
+        }
+        if self
+            .priority
+            .is_some_and(|priority| priority != task.priority)
+        {
+            return false;
+        }
+        let due_ok = match (self.due, task.due) {
+            (DueFilter::Any, _) => true,
+            (_, None) => false,
+            (DueFilter::Overdue, Some(due)) => due < today,
+            (DueFilter::Today, Some(due)) => due <= today,
+            (DueFilter::ThisWeek, Some(due)) => due <= today + Days::new(7),
+            (DueFilter::Before(date), Some(due)) => due < date,
+        };
+        if !due_ok {
+            return false;
+        }
+        match self.status {
+            StatusFilter::All => true,
+            StatusFilter::Incomplete => !task.completed,
+            StatusFilter::Completed => task.completed,
+        }
+    }
+
+    fn is_active(&self) -> bool {
+        *self != Self::default()
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+struct SavedView {
+    name: String,
+    filter: TaskFilter,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct TaskViews {
+    pub current: TaskFilter,
+    views: Vec<SavedView>,
+    #[serde(skip)]
+    new_view_name: String,
+    #[serde(skip)]
+    due_before_text: String,
+    #[serde(skip)]
+    saved_current: TaskFilter, // last persisted filter, to save only on change
+}
+
+impl TaskViews {
+    pub fn new() -> Self {
+        Self {
+            current: TaskFilter::default(),
+            views: vec![SavedView {
+                name: "Today".to_string(),
+                filter: TaskFilter {
+                    due: DueFilter::Today,
+                    status: StatusFilter::Incomplete,
+                    ..Default::default()
+                },
+            }],
+            new_view_name: String::new(),
+            due_before_text: String::new(),
+            saved_current: TaskFilter::default(),
+        }
+    }
+
+    /// Filter bar with saved views. Projects and tags feed the drop-downs.
+    pub fn ui(
+        &mut self,
+        ui: &mut egui::Ui,
+        projects: &[String],
+        tags: &[String],
+        today: NaiveDate,
+    ) {
+        let searching = ui.horizontal(|ui| {
+            let search = ui.add(
+                egui::TextEdit::singleline(&mut self.current.text)
+                    .hint_text("Search tasks")
+                    .desired_width(140.0),
+            );
+
+            egui::ComboBox::from_id_salt("task_view")
+                .selected_text("Views")
+                .width(70.0)
+                .show_ui(ui, |ui| {
+                    let mut to_remove = None;
+                    for (i, view) in self.views.iter().enumerate() {
+                        ui.horizontal(|ui| {
+                            if ui
+                                .selectable_label(self.current == view.filter, &view.name)
+                                .clicked()
+                            {
+                                self.current = view.filter.clone();
+                            }
+                            if ui.small_button("✖").clicked() {
+                                to_remove = Some(i);
+                            }
+                        });
+                    }
+                    if let Some(i) = to_remove {
+                        self.views.remove(i);
+                        self.save();
+                    }
+                    ui.separator();
+                    ui.horizontal(|ui| {
+                        ui.add(
+                            egui::TextEdit::singleline(&mut self.new_view_name)
+                                .hint_text("View name")
+                                .desired_width(90.0),
+                        );
+                        if ui.small_button("Save").clicked()
+                            && !self.new_view_name.trim().is_empty()
+                        {
+                            self.views.push(SavedView {
+                                name: self.new_view_name.trim().to_string(),
+                                filter: self.current.clone(),
+                            });
+                            self.new_view_name.clear();
+                            self.save();
+                        }
+                    });
+                });
+
+            if self.current.is_active() && ui.small_button("Clear").clicked() {
+                self.current = TaskFilter::default();
+            }
+            search.has_focus()
+        });
+
+        ui.horizontal_wrapped(|ui| {
+            option_combo(
+                ui,
+                "filter_project",
+                "Project",
+                &mut self.current.project,
+                projects,
+            );
+            option_combo(ui, "filter_tag", "Tag", &mut self.current.tag, tags);
+
+            egui::ComboBox::from_id_salt("filter_priority")
+                .selected_text(
+                    self.current
+                        .priority
+                        .map_or("Any priority", |priority| priority.label()),
+                )
+                .show_ui(ui, |ui| {
+                    ui.selectable_value(&mut self.current.priority, None, "Any priority");
+                    for priority in Priority::ALL {
+                        ui.selectable_value(
+                            &mut self.current.priority,
+                            Some(priority),
+                            priority.label(),
+                        );
+                    }
+                });
+
+            egui::ComboBox::from_id_salt("filter_due")
+                .selected_text(self.current.due.label())
+                .show_ui(ui, |ui| {
+                    for due in [
+                        DueFilter::Any,
+                        DueFilter::Overdue,
+                        DueFilter::Today,
+                        DueFilter::ThisWeek,
+                    ] {
+                        ui.selectable_value(&mut self.current.due, due, due.label());
+                    }
+                    ui.horizontal(|ui| {
+                        ui.label("Before");
+                        ui.add(
+                            egui::TextEdit::singleline(&mut self.due_before_text)
+                                .hint_text("fri / 2025-06-30")
+                                .desired_width(90.0),
+                        );
+                        if let Some(date) =
+                            crate::quick_add::parse_date(self.due_before_text.trim(), today)
+                        {
+                            if ui.small_button("Set").clicked() {
+                                self.current.due = DueFilter::Before(date);
+                            }
+                        }
+                    });
+                });
+
+            ui.selectable_value(&mut self.current.status, StatusFilter::All, "All");
+            ui.selectable_value(&mut self.current.status, StatusFilter::Incomplete, "Open");
+            ui.selectable_value(&mut self.current.status, StatusFilter::Completed, "Done");
+        });
+
+        // Typed searches are saved once the search box loses focus, not on every keystroke
+        if self.current != self.saved_current && !searching.inner {
+            self.save();
+            self.saved_current = self.current.clone();
+        }
+    }
+
+    pub fn save(&self) {
+        if let Some(home_dir) = dirs::home_dir() {
+            let backup_dir = home_dir.join(".rust_pomodoro_backup");
+            fs::create_dir_all(&backup_dir).unwrap_or(());
+            let views_json = serde_json::to_string(&self).unwrap_or_default();
+            fs::write(backup_dir.join("views.json"), views_json).unwrap_or(());
+        }
+    }
+
+    pub fn load() -> Self {
+        if let Some(home_dir) = dirs::home_dir() {
+            let file_path = home_dir.join(".rust_pomodoro_backup/views.json");
+            if Path::new(&file_path).exists() {
+                if let Ok(contents) = fs::read_to_string(file_path) {
+                    if let Ok(views) = serde_json::from_str::<TaskViews>(&contents) {
+                        return Self {
+                            saved_current: views.current.clone(),
+                            ..views
+                        };
+                    }
+                }
+            }
+        }
+        Self::new()
+    }
+}
+
+fn option_combo(
+    ui: &mut egui::Ui,
+    id_salt: &str,
+    any_label: &str,
+    value: &mut Option<String>,
+    options: &[String],
+) {
+    let selected = match value {
+        Some(option) => option.clone(),
+        None => format!("Any {}", any_label.to_lowercase()),
+    };
+    egui::ComboBox::from_id_salt(id_salt)
+        .selected_text(selected)
+        .show_ui(ui, |ui| {
+            ui.selectable_value(value, None, RichText::new("Any").italics());
+            for option in options {
+                ui.selectable_value(value, Some(option.clone()), option);
+            }
+        });
+}
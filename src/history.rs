@@ -0,0 +1,95 @@
+use std::time::{Duration, Instant};
+
+use crate::pomodoro::PomodoroSnapshot;
+use crate::task::TasksSnapshot;
+use crate::timer::TimerSnapshot;
+
+const MAX_ENTRIES: usize = 100;
+const TOAST_DURATION: Duration = Duration::from_secs(5);
+
+/// State captured before an action, enough to put things back the way they were.
+pub enum Snapshot {
+    Tasks(TasksSnapshot),
+    Pomodoro(PomodoroSnapshot),
+    Timer(TimerSnapshot),
+}
+
+pub struct Entry {
+    pub label: String,
+    pub snapshot: Snapshot,
+}
+
+pub struct History {
+    undo: Vec<Entry>,
+    redo: Vec<Entry>,
+    toast: Option<(String, Instant)>, // label of the last destructive action
+}
+
+impl History {
+    pub fn new() -> Self {
+        Self {
+            undo: Vec::new(),
+            redo: Vec::new(),
+            toast: None,
+        }
+    }
+
+    pub fn record(&mut self, label: &str, snapshot: Snapshot) {
+        self.undo.push(Entry {
+            label: label.to_string(),
+            snapshot,
+        });
+        if self.undo.len() > MAX_ENTRIES {
+            self.undo.remove(0);
+        }
+        self.redo.clear();
+        // A toast left over from an earlier action would now undo this one instead
+        self.toast = None;
+    }
+
+    /// Records an action that throws something away and offers an "Undo" toast for it.
+    pub fn record_destructive(&mut self, label: &str, snapshot: Snapshot) {
+        self.record(label, snapshot);
+        self.toast = Some((label.to_string(), Instant::now()));
+    }
+
+    pub fn pop_undo(&mut self) -> Option<Entry> {
+        self.toast = None;
+        self.undo.pop()
+    }
+
+    /// Forgets every entry, for when something changed that older snapshots would undo.
+    pub fn clear(&mut self) {
+        self.undo.clear();
+        self.redo.clear();
+        self.toast = None;
+    }
+
+    pub fn pop_redo(&mut self) -> Option<Entry> {
+        self.redo.pop()
+    }
+
+    pub fn push_undo(&mut self, entry: Entry) {
+        self.undo.push(entry);
+    }
+
+    pub fn push_redo(&mut self, entry: Entry) {
+        self.redo.push(entry);
+    }
+
+    /// Label of the action the toast should offer to undo, while it is still showing.
+    pub fn toast(&mut self) -> Option<&str> {
+        if self
+            .toast
+            .as_ref()
+            .is_some_and(|(_, shown)| shown.elapsed() > TOAST_DURATION)
+        {
+            self.toast = None;
+        }
+        self.toast.as_ref().map(|(label, _)| label.as_str())
+    }
+
+    pub fn dismiss_toast(&mut self) {
+        self.toast = None;
+    }
+}
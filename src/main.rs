@@ -1,11 +1,28 @@
+mod alerts;
+mod ambient;
 mod app;
+mod archive;
+mod board;
 mod clock;
+mod commitment;
+mod filter;
+mod history;
+mod music;
+mod planner;
 mod pomodoro;
+mod quick_add;
+mod recurring;
+mod reflection;
+mod reminder;
+mod rollover;
 mod sound;
+mod sound_settings;
 mod stats;
 mod task;
 mod theme;
 mod timer;
+mod tone;
+mod typography;
 
 use app::ClockApp;
 use egui::ViewportBuilder;
@@ -17,15 +34,8 @@ fn main() -> eframe::Result<()> {
     };
 
     eframe::run_native(
-        "Rust Clock",
+        alerts::APP_TITLE,
         options,
-        Box::new(|cc| {
-            // Set custom fonts if needed
-
-            // Set default theme
-            theme::set_theme(&cc.egui_ctx);
-
-            Ok(Box::new(ClockApp::new(cc)))
-        }),
+        Box::new(|cc| Ok(Box::new(ClockApp::new(cc)))),
     )
 }
@@ -0,0 +1,80 @@
+// A playlist of the user's own audio files for work phases: every file in a folder, or the
+// entries of an M3U playlist. Playback itself lives on the audio thread in sound.rs.
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use crate::sound;
+
+const EXTENSIONS: [&str; 4] = ["wav", "ogg", "flac", "mp3"];
+
+/// The tracks `source` points at, in play order.
+pub fn load(source: &str) -> Result<Vec<PathBuf>, String> {
+    let path = Path::new(source.trim());
+    let tracks = if path.is_dir() {
+        let entries = fs::read_dir(path).map_err(|_| "Folder cannot be read".to_string())?;
+        let mut tracks: Vec<PathBuf> = entries
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| is_audio(path))
+            .collect();
+        tracks.sort();
+        tracks
+    } else if is_playlist(path) {
+        let contents = fs::read_to_string(path).map_err(|_| "Playlist not found".to_string())?;
+        let base = path.parent().unwrap_or(Path::new(""));
+        contents
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .map(|line| base.join(line)) // relative entries are relative to the playlist
+            .filter(|path| path.is_file())
+            .collect()
+    } else {
+        return Err("Not a folder or an .m3u playlist".to_string());
+    };
+
+    if tracks.is_empty() {
+        return Err("No audio files found".to_string());
+    }
+    Ok(tracks)
+}
+
+/// The file name without its extension, for showing what is playing.
+pub fn track_name(path: &Path) -> String {
+    path.file_stem()
+        .map(|stem| stem.to_string_lossy().to_string())
+        .unwrap_or_default()
+}
+
+fn is_audio(path: &Path) -> bool {
+    has_extension(path, &EXTENSIONS)
+}
+
+fn is_playlist(path: &Path) -> bool {
+    has_extension(path, &["m3u", "m3u8"])
+}
+
+fn has_extension(path: &Path, extensions: &[&str]) -> bool {
+    path.extension()
+        .and_then(|extension| extension.to_str())
+        .is_some_and(|extension| extensions.contains(&extension.to_lowercase().as_str()))
+}
+
+/// What is playing with previous and next buttons, for the Pomodoro view.
+pub fn controls_ui(ui: &mut egui::Ui) {
+    ui.horizontal(|ui| {
+        if ui
+            .small_button("⏮")
+            .on_hover_text("Previous track")
+            .clicked()
+        {
+            sound::skip_track(-1);
+        }
+        if ui.small_button("⏭").on_hover_text("Next track").clicked() {
+            sound::skip_track(1);
+        }
+        let playing = sound::now_playing().unwrap_or_else(|| "No track".to_string());
+        ui.label(egui::RichText::new(format!("🎵 {}", playing)).weak());
+    });
+}
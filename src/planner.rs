@@ -0,0 +1,335 @@
+// File: src/planner.rs
+use chrono::{DateTime, Local, NaiveTime, TimeDelta};
+use egui::{RichText, Ui};
+use serde::{Deserialize, Serialize};
+use std::{fs, path::Path, time::Duration};
+
+use crate::pomodoro::{Pomodoro, PomodoroState};
+use crate::theme;
+
+/// A task as the planner sees it: how many work sessions are left and how long each is.
+pub struct PlanItem {
+    pub name: String,
+    pub sessions: u32,
+    pub duration: Duration,
+    pub running: bool,
+}
+
+pub struct Slot {
+    pub phase: PomodoroState,
+    pub task: Option<String>,
+    pub start: DateTime<Local>,
+    pub end: DateTime<Local>,
+}
+
+/// Projects the current phase and every remaining task session onto the clock, starting
+/// `now` and inserting short/long breaks the way the pomodoro cycle would. Lengths are
+/// stretched by the recent pace so sessions that keep running long push the plan out.
+pub fn schedule(pomodoro: &Pomodoro, items: &[PlanItem], now: DateTime<Local>) -> Vec<Slot> {
+    let profile = pomodoro.profile();
+    let pace = pomodoro.pace();
+    let mut cycles = pomodoro.completed_cycles();
+    let mut slots = Vec::new();
+    let mut t = now;
+
+    let mut sessions: Vec<(Option<&str>, Duration)> = items
+        .iter()
+        .flat_map(|item| (0..item.sessions).map(move |_| (Some(item.name.as_str()), item.duration)))
+        .collect();
+
+    let mut push = |phase, task: Option<&str>, length: Duration, t: &mut DateTime<Local>| {
+        let end = *t + TimeDelta::from_std(length.mul_f32(pace)).unwrap_or_default();
+        slots.push(Slot {
+            phase,
+            task: task.map(str::to_string),
+            start: *t,
+            end,
+        });
+        *t = end;
+    };
+
+    // Finish whatever phase is underway first
+    match pomodoro.phase() {
+        PomodoroState::Work => {
+            let task = match items.first() {
+                Some(item) if item.running && !sessions.is_empty() => sessions.remove(0).0,
+                _ => None,
+            };
+            push(PomodoroState::Work, task, pomodoro.remaining_time(), &mut t);
+            cycles += 1;
+            if !sessions.is_empty() {
+                let (phase, length) = next_break(cycles, &profile);
+                push(phase, None, length, &mut t);
+            }
+        }
+        phase @ (PomodoroState::ShortBreak | PomodoroState::LongBreak) => {
+            push(phase, None, pomodoro.remaining_time(), &mut t);
+        }
+        PomodoroState::Stopped | PomodoroState::Paused => {}
+    }
+
+    let count = sessions.len();
+    for (i, (task, length)) in sessions.into_iter().enumerate() {
+        push(PomodoroState::Work, task, length, &mut t);
+        cycles += 1;
+        if i + 1 < count {
+            let (phase, length) = next_break(cycles, &profile);
+            push(phase, None, length, &mut t);
+        }
+    }
+
+    slots
+}
+
+fn next_break(cycles: u32, profile: &crate::pomodoro::Profile) -> (PomodoroState, Duration) {
+    if cycles % profile.cycles_before_long_break == 0 {
+        (PomodoroState::LongBreak, profile.long_break)
+    } else {
+        (PomodoroState::ShortBreak, profile.short_break)
+    }
+}
+
+/// Upcoming phases, finish times and cycle position for the Pomodoro tab.
+pub fn forecast_ui(ui: &mut Ui, pomodoro: &Pomodoro, items: &[PlanItem]) {
+    let profile = pomodoro.profile();
+    let per_cycle = profile.cycles_before_long_break.max(1);
+    let done_in_cycle = pomodoro.completed_cycles() % per_cycle;
+    let until_long_break = per_cycle - done_in_cycle;
+
+    if pomodoro.phase() == PomodoroState::Stopped {
+        ui.label(RichText::new("Start a session to see the forecast").weak());
+        return;
+    }
+
+    ui.label(format!(
+        "{} until the next long break",
+        match until_long_break {
+            1 => "1 pomodoro".to_string(),
+            n => format!("{} pomodoros", n),
+        }
+    ));
+    if pomodoro.pace() > 1.01 {
+        ui.label(
+            RichText::new(format!(
+                "Sessions are running {:.0}% over plan",
+                (pomodoro.pace() - 1.0) * 100.0
+            ))
+            .weak(),
+        );
+    }
+
+    // Without tasks, project plain sessions up to and including the next long break
+    let generic;
+    let items = if items.is_empty() {
+        generic = [PlanItem {
+            name: String::new(),
+            sessions: until_long_break,
+            duration: profile.work,
+            running: false,
+        }];
+        &generic[..]
+    } else {
+        items
+    };
+
+    let slots = schedule(pomodoro, items, Local::now());
+    ui.add_space(5.0);
+    for slot in slots.iter().skip(1).take(6) {
+        ui.horizontal(|ui| {
+            ui.label(
+                RichText::new(format!(
+                    "{} at {}",
+                    slot.phase.label(),
+                    slot.start.format("%H:%M")
+                ))
+                .color(slot.phase.color()),
+            );
+            if let Some(task) = slot.task.as_deref().filter(|task| !task.is_empty()) {
+                ui.label(RichText::new(task).weak());
+            }
+        });
+    }
+
+    if let Some(active) = items.iter().find(|item| item.running) {
+        if let Some(slot) = slots
+            .iter()
+            .rev()
+            .find(|slot| slot.task.as_deref() == Some(active.name.as_str()))
+        {
+            ui.add_space(5.0);
+            ui.label(format!(
+                "\"{}\" done at {}",
+                active.name,
+                slot.end.format("%H:%M")
+            ));
+        }
+    }
+    if items.iter().any(|item| !item.name.is_empty()) {
+        if let Some(last) = slots
+            .iter()
+            .rev()
+            .find(|slot| slot.phase == PomodoroState::Work)
+        {
+            ui.label(format!("All tasks done at {}", last.end.format("%H:%M")));
+        }
+    }
+}
+
+pub fn format_span(delta: TimeDelta) -> String {
+    let minutes = delta.num_minutes().abs();
+    if minutes >= 60 {
+        format!("{}h {}m", minutes / 60, minutes % 60)
+    } else {
+        format!("{}m", minutes)
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct Planner {
+    start_hour: u32,
+    start_minute: u32,
+    end_hour: u32,
+    end_minute: u32,
+}
+
+impl Planner {
+    pub fn new() -> Self {
+        Self {
+            start_hour: 9,
+            start_minute: 0,
+            end_hour: 18,
+            end_minute: 0,
+        }
+    }
+
+    fn working_hours(&self) -> (NaiveTime, NaiveTime) {
+        let start = NaiveTime::from_hms_opt(self.start_hour, self.start_minute, 0)
+            .unwrap_or(NaiveTime::MIN);
+        let end =
+            NaiveTime::from_hms_opt(self.end_hour, self.end_minute, 0).unwrap_or(NaiveTime::MIN);
+        (start, end)
+    }
+
+    pub fn ui(&mut self, ui: &mut Ui, pomodoro: &Pomodoro, items: &[PlanItem]) {
+        ui.vertical_centered(|ui| {
+            ui.add_space(10.0);
+            ui.heading("Day Planner");
+            ui.add_space(10.0);
+        });
+
+        let mut changed = false;
+        ui.horizontal(|ui| {
+            ui.label("Working hours:");
+            changed |= time_input(ui, &mut self.start_hour, &mut self.start_minute);
+            ui.label("to");
+            changed |= time_input(ui, &mut self.end_hour, &mut self.end_minute);
+        });
+        if changed {
+            self.save();
+        }
+        ui.add_space(10.0);
+
+        let (day_start, day_end) = self.working_hours();
+        let mut now = Local::now();
+        if now.time() < day_start {
+            if let Some(start) = now.with_time(day_start).single() {
+                now = start;
+            }
+        }
+        let day_end = now.with_time(day_end).single().unwrap_or(now);
+
+        let slots = schedule(pomodoro, items, now);
+        let Some(last) = slots.last() else {
+            ui.label(
+                RichText::new("Nothing planned. Add tasks with estimates to fill the day.").weak(),
+            );
+            return;
+        };
+
+        let overflow = slots
+            .iter()
+            .filter(|slot| slot.phase == PomodoroState::Work && slot.end > day_end)
+            .count();
+        if last.end <= day_end {
+            ui.colored_label(
+                PomodoroState::ShortBreak.color(),
+                format!(
+                    "Done at {} — fits before {} with {} to spare",
+                    last.end.format("%H:%M"),
+                    day_end.format("%H:%M"),
+                    format_span(day_end - last.end)
+                ),
+            );
+        } else {
+            ui.colored_label(
+                PomodoroState::Work.color(),
+                format!(
+                    "Done at {} — runs {} past {}, {} work sessions don't fit",
+                    last.end.format("%H:%M"),
+                    format_span(last.end - day_end),
+                    day_end.format("%H:%M"),
+                    overflow
+                ),
+            );
+        }
+        ui.add_space(10.0);
+
+        egui::ScrollArea::vertical().show(ui, |ui| {
+            egui::Grid::new("planner_timeline")
+                .striped(true)
+                .spacing([20.0, 6.0])
+                .show(ui, |ui| {
+                    for slot in &slots {
+                        let after_hours = slot.end > day_end;
+                        let time = format!(
+                            "{} – {}",
+                            slot.start.format("%H:%M"),
+                            slot.end.format("%H:%M")
+                        );
+                        ui.label(if after_hours {
+                            RichText::new(time).color(theme::palette().muted).italics()
+                        } else {
+                            RichText::new(time)
+                        });
+                        ui.label(RichText::new(slot.phase.label()).color(slot.phase.color()));
+                        ui.label(slot.task.as_deref().unwrap_or(""));
+                        ui.end_row();
+                    }
+                });
+        });
+    }
+
+    pub fn save(&self) {
+        if let Some(home_dir) = dirs::home_dir() {
+            let backup_dir = home_dir.join(".rust_pomodoro_backup");
+            fs::create_dir_all(&backup_dir).unwrap_or(());
+            let planner_json = serde_json::to_string(&self).unwrap_or_default();
+            fs::write(backup_dir.join("planner.json"), planner_json).unwrap_or(());
+        }
+    }
+
+    pub fn load() -> Self {
+        if let Some(home_dir) = dirs::home_dir() {
+            let file_path = home_dir.join(".rust_pomodoro_backup/planner.json");
+            if Path::new(&file_path).exists() {
+                if let Ok(contents) = fs::read_to_string(file_path) {
+                    if let Ok(planner) = serde_json::from_str(&contents) {
+                        return planner;
+                    }
+                }
+            }
+        }
+        Self::new()
+    }
+}
+
+fn time_input(ui: &mut Ui, hour: &mut u32, minute: &mut u32) -> bool {
+    let hour_changed = ui
+        .add(egui::DragValue::new(hour).range(0..=23).speed(0.1))
+        .changed();
+    ui.label(":");
+    let minute_changed = ui
+        .add(egui::DragValue::new(minute).range(0..=59).speed(0.2))
+        .changed();
+    hour_changed || minute_changed
+}
@@ -1,5 +1,8 @@
 // File: src/pomodoro.rs
-use crate::sound::play_notification;
+use crate::history::{History, Snapshot};
+use crate::sound::{self, SoundEvent};
+use crate::theme;
+use crate::typography;
 use eframe::egui;
 use egui::{Align2, Color32, FontId, RichText, Sense, Stroke, Ui, Vec2};
 use serde::{Deserialize, Serialize};
@@ -15,7 +18,7 @@ pub enum PomodoroState {
 }
 
 impl PomodoroState {
-    fn label(&self) -> &'static str {
+    pub fn label(&self) -> &'static str {
         match self {
             PomodoroState::Stopped => "Stopped",
             PomodoroState::Work => "Work",
@@ -25,17 +28,35 @@ impl PomodoroState {
         }
     }
 
-    fn color(&self) -> Color32 {
+    pub fn color(&self) -> Color32 {
+        let palette = theme::palette();
         match self {
-            PomodoroState::Stopped => Color32::GRAY,
-            PomodoroState::Work => Color32::from_rgb(235, 87, 87), // Red for work
-            PomodoroState::ShortBreak => Color32::from_rgb(106, 176, 76), // Green for short break
-            PomodoroState::LongBreak => Color32::from_rgb(79, 134, 198), // Blue for long break
-            PomodoroState::Paused => Color32::GRAY,
+            PomodoroState::Stopped | PomodoroState::Paused => palette.idle,
+            PomodoroState::Work => palette.work,
+            PomodoroState::ShortBreak => palette.short_break,
+            PomodoroState::LongBreak => palette.long_break,
         }
     }
 }
 
+/// Phase lengths, used to project upcoming sessions onto the clock.
+#[derive(Clone, Copy)]
+pub struct Profile {
+    pub work: Duration,
+    pub short_break: Duration,
+    pub long_break: Duration,
+    pub cycles_before_long_break: u32,
+}
+
+/// Timer position saved before Stop/Reset so they can be undone.
+pub struct PomodoroSnapshot {
+    state: PomodoroState,
+    elapsed: Duration,
+    previous_state: Option<PomodoroState>,
+    completed_cycles: u32,
+    phase_wall_time: Duration,
+}
+
 #[derive(Serialize, Deserialize)]
 pub struct Metrics {
     pub completed_pomodoros: u32,
@@ -55,6 +76,10 @@ pub struct Pomodoro {
     completed_pomodoros: u32,
     pub metrics: Metrics,
     previous_state: Option<PomodoroState>,
+    work_completed: bool, // set when a work session finishes, cleared by take_work_completed
+    phase_wall_time: Duration, // wall-clock time spent in the current phase, pauses included
+    pace: f32,            // smoothed wall-clock / planned length of finished phases
+    transition: Option<PomodoroState>, // phase that just began, cleared by take_transition
 }
 
 impl Pomodoro {
@@ -77,6 +102,10 @@ impl Pomodoro {
             completed_pomodoros: metrics.completed_pomodoros,
             metrics,
             previous_state: None,
+            work_completed: false,
+            phase_wall_time: Duration::from_secs(0),
+            pace: 1.0,
+            transition: None,
         }
     }
 
@@ -104,6 +133,10 @@ impl Pomodoro {
     }
 
     pub fn update(&mut self, elapsed: Duration, ctx: &egui::Context) {
+        if self.state != PomodoroState::Stopped {
+            self.phase_wall_time += elapsed;
+        }
+
         if self.state == PomodoroState::Stopped || self.state == PomodoroState::Paused {
             return; // we do not update elapsed time when stopped or paused
         }
@@ -121,6 +154,7 @@ impl Pomodoro {
             let next_state = match self.state {
                 PomodoroState::Work => {
                     self.completed_cycles += 1;
+                    self.work_completed = true;
                     self.metrics.completed_pomodoros += 1;
                     self.metrics.total_work_time += current_duration;
                     if self.completed_cycles % self.cycles_before_long_break == 0 {
@@ -133,14 +167,24 @@ impl Pomodoro {
                 PomodoroState::Stopped | PomodoroState::Paused => unreachable!(),
             };
 
+            // Phases that ran long (pauses) slow the forecast down
+            let sample = self.phase_wall_time.as_secs_f32() / current_duration.as_secs_f32();
+            self.pace = (0.7 * self.pace + 0.3 * sample).clamp(1.0, 3.0);
+            self.phase_wall_time = Duration::from_secs(0);
+
             // Reset elapsed time
             self.elapsed = Duration::from_secs(0);
 
             // Update state
             self.state = next_state;
+            self.transition = Some(next_state);
 
             // Play notification sound
-            play_notification();
+            sound::play(match next_state {
+                PomodoroState::ShortBreak => SoundEvent::ShortBreak,
+                PomodoroState::LongBreak => SoundEvent::LongBreak,
+                _ => SoundEvent::WorkStart,
+            });
 
             // Show notification
             self.show_notification = true;
@@ -154,6 +198,53 @@ impl Pomodoro {
         }
     }
 
+    pub fn snapshot(&self) -> PomodoroSnapshot {
+        PomodoroSnapshot {
+            state: self.state,
+            elapsed: self.elapsed,
+            previous_state: self.previous_state,
+            completed_cycles: self.completed_cycles,
+            phase_wall_time: self.phase_wall_time,
+        }
+    }
+
+    pub fn restore(&mut self, snapshot: PomodoroSnapshot) {
+        self.state = snapshot.state;
+        self.elapsed = snapshot.elapsed;
+        self.previous_state = snapshot.previous_state;
+        self.completed_cycles = snapshot.completed_cycles;
+        self.phase_wall_time = snapshot.phase_wall_time;
+    }
+
+    /// Stops the timer, dropping the current phase.
+    pub fn stop(&mut self) {
+        self.state = PomodoroState::Stopped;
+        self.previous_state = None; // Reset previous state on stop
+        self.phase_wall_time = Duration::from_secs(0);
+    }
+
+    /// The phase that began when the last one ran out, returned once.
+    pub fn take_transition(&mut self) -> Option<PomodoroState> {
+        self.transition.take()
+    }
+
+    /// Remaining time and phase for the window title, while the timer is in use.
+    pub fn title_status(&self) -> Option<String> {
+        if self.state == PomodoroState::Stopped {
+            return None;
+        }
+        Some(format!(
+            "{} · {}",
+            Self::format_time(self.remaining_time()),
+            self.state.label()
+        ))
+    }
+
+    /// Returns true once for every work session that ran to completion.
+    pub fn take_work_completed(&mut self) -> bool {
+        std::mem::take(&mut self.work_completed)
+    }
+
     fn current_duration(&self) -> Duration {
         match self.state {
             PomodoroState::Stopped => Duration::from_secs(0),
@@ -172,7 +263,42 @@ impl Pomodoro {
         }
     }
 
-    fn remaining_time(&self) -> Duration {
+    pub fn profile(&self) -> Profile {
+        Profile {
+            work: self.work_duration,
+            short_break: self.short_break_duration,
+            long_break: self.long_break_duration,
+            cycles_before_long_break: self.cycles_before_long_break,
+        }
+    }
+
+    /// Starts a fresh work session of the given length.
+    pub fn start_work(&mut self, duration: Duration) {
+        self.work_duration = duration;
+        self.state = PomodoroState::Work;
+        self.elapsed = Duration::from_secs(0);
+        self.previous_state = None; // Reset previous state on new start
+        self.phase_wall_time = Duration::from_secs(0);
+    }
+
+    /// How much longer than planned phases have been taking lately (1.0 = on time).
+    pub fn pace(&self) -> f32 {
+        self.pace
+    }
+
+    pub fn completed_cycles(&self) -> u32 {
+        self.completed_cycles
+    }
+
+    /// The phase the timer is in, looking through a pause to the phase underneath.
+    pub fn phase(&self) -> PomodoroState {
+        match self.state {
+            PomodoroState::Paused => self.previous_state.unwrap_or(PomodoroState::Stopped),
+            state => state,
+        }
+    }
+
+    pub fn remaining_time(&self) -> Duration {
         let current_duration = self.current_duration();
         if self.elapsed > current_duration {
             Duration::from_secs(0)
@@ -201,7 +327,7 @@ impl Pomodoro {
         self.elapsed.as_secs_f32() / current_duration.as_secs_f32()
     }
 
-    pub fn ui(&mut self, ui: &mut Ui) {
+    pub fn ui(&mut self, ui: &mut Ui, history: &mut History) {
         ui.vertical_centered(|ui| {
             ui.add_space(10.0);
             ui.heading("Pomodoro Timer");
@@ -219,7 +345,8 @@ impl Pomodoro {
             );
 
             // Timer circle
-            let timer_size = Vec2::splat(ui.available_width().min(240.0));
+            let ring = typography::ring_size(ui);
+            let timer_size = Vec2::splat(ring);
             let (response, painter) = ui.allocate_painter(timer_size, Sense::hover());
             let center = response.rect.center();
             let radius = response.rect.width() / 2.0 - 10.0;
@@ -230,7 +357,7 @@ impl Pomodoro {
                 let angle = std::f32::consts::TAU * progress - std::f32::consts::FRAC_PI_2;
 
                 // Background circle
-                painter.circle_stroke(center, radius, Stroke::new(5.0, Color32::DARK_GRAY));
+                painter.circle_stroke(center, radius, Stroke::new(5.0, theme::palette().dim));
 
                 // Progress arc (we'll approximate with line segments)
                 let segments = 100;
@@ -263,11 +390,11 @@ impl Pomodoro {
                 center,
                 Align2::CENTER_CENTER,
                 remaining,
-                FontId::proportional(32.0),
+                typography::countdown_font(ring),
                 if self.state == PomodoroState::Stopped {
-                    Color32::GRAY
+                    theme::palette().muted
                 } else {
-                    Color32::WHITE
+                    theme::palette().strong
                 },
             );
 
@@ -283,19 +410,20 @@ impl Pomodoro {
 
                 if ui.button(button_text).clicked() {
                     if self.state == PomodoroState::Stopped {
-                        self.state = PomodoroState::Work;
-                        self.elapsed = Duration::from_secs(0);
-                        self.previous_state = None; // Reset previous state on new start
+                        self.start_work(self.work_duration);
                     } else {
-                        self.state = PomodoroState::Stopped;
-                        self.previous_state = None; // Reset previous state on stop
+                        history.record_destructive(
+                            "Stop pomodoro",
+                            Snapshot::Pomodoro(self.snapshot()),
+                        );
+                        self.stop();
                     }
                 }
 
                 if self.state != PomodoroState::Stopped && self.state != PomodoroState::Paused {
                     if ui.button("Pause").clicked() {
-                        self.state = PomodoroState::Paused;
                         self.previous_state = Some(self.state); // Store the current state before pausing
+                        self.state = PomodoroState::Paused;
                     }
                 }
 
@@ -308,7 +436,12 @@ impl Pomodoro {
 
                 if self.state != PomodoroState::Stopped {
                     if ui.button("Reset").clicked() {
+                        history.record_destructive(
+                            "Reset pomodoro",
+                            Snapshot::Pomodoro(self.snapshot()),
+                        );
                         self.elapsed = Duration::from_secs(0);
+                        self.phase_wall_time = Duration::from_secs(0);
                         if self.state == PomodoroState::Paused {
                             self.state = self.previous_state.unwrap_or(PomodoroState::Work); // Resume if reset while paused
                             self.previous_state = None;
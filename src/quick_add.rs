@@ -0,0 +1,222 @@
+// Single-line task entry, e.g. `Write migration guide +docs #high ~3p 45m due:fri at:14:00`
+use chrono::{Datelike, Days, NaiveDate, NaiveTime, Weekday};
+use std::time::Duration;
+
+use crate::task::Priority;
+
+#[derive(Default)]
+pub struct QuickAdd {
+    pub name: String,
+    pub project: Option<String>,
+    pub tags: Vec<String>,
+    pub priority: Option<Priority>,
+    pub estimate: Option<u32>,
+    pub duration: Option<Duration>,
+    pub due: Option<NaiveDate>,
+    pub time: Option<NaiveTime>,
+}
+
+/// Parses a quick-add line. Plain words make up the name; every token that looks like
+/// an attribute but cannot be understood is reported as an error.
+pub fn parse(input: &str, today: NaiveDate) -> (QuickAdd, Vec<String>) {
+    let mut parsed = QuickAdd::default();
+    let mut errors = Vec::new();
+    let mut name_words = Vec::new();
+
+    for token in input.split_whitespace() {
+        if let Some(project) = token.strip_prefix('+') {
+            if project.is_empty() {
+                errors.push("`+` needs a project name".to_string());
+            } else {
+                parsed.project = Some(project.to_string());
+            }
+        } else if let Some(tag) = token.strip_prefix('#') {
+            if tag.is_empty() {
+                errors.push("`#` needs a tag or priority".to_string());
+            } else if let Some(priority) = Priority::parse(tag) {
+                parsed.priority = Some(priority);
+            } else {
+                parsed.tags.push(tag.to_lowercase());
+            }
+        } else if let Some(estimate) = token.strip_prefix('~') {
+            match parse_estimate(estimate) {
+                Some(n) => parsed.estimate = Some(n),
+                None => errors.push(format!("`{}` is not a pomodoro estimate like ~3p", token)),
+            }
+        } else if let Some(date) = token.strip_prefix("due:") {
+            match parse_due(date, today) {
+                Ok(date) => parsed.due = Some(date),
+                Err(error) => errors.push(error),
+            }
+        } else if let Some(time) = token.strip_prefix("at:") {
+            match parse_at(time) {
+                Ok(time) => parsed.time = Some(time),
+                Err(error) => errors.push(error),
+            }
+        } else if let Some(duration) = parse_duration(token) {
+            parsed.duration = Some(duration);
+        } else {
+            name_words.push(token);
+        }
+    }
+
+    parsed.name = name_words.join(" ");
+    if parsed.name.is_empty() && !input.trim().is_empty() {
+        errors.push("Task name is missing".to_string());
+    }
+    (parsed, errors)
+}
+
+/// `3` or `3p` after the `~`, at least one.
+fn parse_estimate(text: &str) -> Option<u32> {
+    let digits = text.strip_suffix('p').unwrap_or(text);
+    if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    digits.parse::<u32>().ok().filter(|n| *n > 0)
+}
+
+/// `45m`, `1h`, `1h30m`. Anything else, including bare numbers, is not a duration.
+fn parse_duration(token: &str) -> Option<Duration> {
+    let (hours, rest) = match token.split_once('h') {
+        Some((hours, rest)) => (hours.parse::<u64>().ok()?, rest),
+        None => (0, token),
+    };
+    let minutes = if rest.is_empty() {
+        0
+    } else {
+        rest.strip_suffix('m')?.parse::<u64>().ok()?
+    };
+    let total = hours * 60 + minutes;
+    (total > 0 && total <= 24 * 60).then(|| Duration::from_secs(total * 60))
+}
+
+/// A due date as `parse_date` reads it, with the message shown when it cannot be read.
+pub fn parse_due(text: &str, today: NaiveDate) -> Result<NaiveDate, String> {
+    parse_date(text, today).ok_or_else(|| format!("`{}` is not a date I understand", text))
+}
+
+/// `today`, `tomorrow`, a weekday (`fri`, `friday`; today counts) or `YYYY-MM-DD`.
+pub fn parse_date(text: &str, today: NaiveDate) -> Option<NaiveDate> {
+    match text.to_lowercase().as_str() {
+        "today" | "tod" => Some(today),
+        "tomorrow" | "tom" => today.checked_add_days(Days::new(1)),
+        other => {
+            if let Ok(weekday) = other.parse::<Weekday>() {
+                let ahead = (7 + weekday.num_days_from_monday()
+                    - today.weekday().num_days_from_monday())
+                    % 7;
+                return today.checked_add_days(Days::new(ahead as u64));
+            }
+            NaiveDate::parse_from_str(other, "%Y-%m-%d").ok()
+        }
+    }
+}
+
+/// A time of day as `parse_time` reads it, with the message shown when it cannot be read.
+pub fn parse_at(text: &str) -> Result<NaiveTime, String> {
+    parse_time(text).ok_or_else(|| format!("`{}` is not a time like 14:30", text))
+}
+
+/// `14:30` or `9:05`.
+pub fn parse_time(text: &str) -> Option<NaiveTime> {
+    NaiveTime::parse_from_str(text, "%H:%M").ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    #[test]
+    fn parses_every_attribute_and_keeps_the_rest_as_the_name() {
+        let today = date(2026, 6, 30); // a Tuesday
+        let (parsed, errors) = parse(
+            "Write migration guide +docs #High #writing ~3p 1h30m due:fri at:9:05",
+            today,
+        );
+        assert!(errors.is_empty(), "{:?}", errors);
+        assert_eq!(parsed.name, "Write migration guide");
+        assert_eq!(parsed.project.as_deref(), Some("docs"));
+        assert!(parsed.priority == Some(Priority::High));
+        assert_eq!(parsed.tags, vec!["writing".to_string()]);
+        assert_eq!(parsed.estimate, Some(3));
+        assert_eq!(parsed.duration, Some(Duration::from_secs(90 * 60)));
+        assert_eq!(parsed.due, Some(date(2026, 7, 3)));
+        assert_eq!(parsed.time, NaiveTime::from_hms_opt(9, 5, 0));
+    }
+
+    #[test]
+    fn reports_each_bad_token() {
+        let (parsed, errors) = parse("Call + # ~xp due:someday at:25:00", date(2026, 6, 30));
+        assert_eq!(parsed.name, "Call");
+        assert_eq!(errors.len(), 5, "{:?}", errors);
+        assert!(parsed.project.is_none() && parsed.estimate.is_none());
+        assert!(parsed.due.is_none() && parsed.time.is_none());
+    }
+
+    #[test]
+    fn estimates_are_a_number_with_an_optional_p() {
+        let today = date(2026, 6, 30);
+        assert_eq!(parse("Plan ~2", today).0.estimate, Some(2));
+        assert_eq!(parse("Plan ~12p", today).0.estimate, Some(12));
+        for token in ["~3ppp", "~p", "~0p", "~+3", "~3pp", "~ 3"] {
+            let (parsed, errors) = parse(&format!("Plan {}", token), today);
+            assert!(parsed.estimate.is_none(), "{}", token);
+            assert!(!errors.is_empty(), "{}", token);
+        }
+    }
+
+    #[test]
+    fn attributes_alone_are_missing_a_name() {
+        let (_, errors) = parse("+home 30m", date(2026, 6, 30));
+        assert_eq!(errors, vec!["Task name is missing".to_string()]);
+        assert!(parse("   ", date(2026, 6, 30)).1.is_empty());
+    }
+
+    #[test]
+    fn words_that_are_not_durations_stay_in_the_name() {
+        let (parsed, errors) = parse("Read 3 chapters 0m 25h", date(2026, 6, 30));
+        assert!(errors.is_empty());
+        assert_eq!(parsed.name, "Read 3 chapters 0m 25h");
+        assert!(parsed.duration.is_none());
+    }
+
+    #[test]
+    fn relative_dates_roll_over_month_and_year_ends() {
+        assert_eq!(
+            parse_date("tomorrow", date(2026, 1, 31)),
+            Some(date(2026, 2, 1))
+        );
+        assert_eq!(
+            parse_date("tom", date(2028, 2, 28)),
+            Some(date(2028, 2, 29))
+        );
+        // 2026-12-31 is a Thursday
+        assert_eq!(
+            parse_date("fri", date(2026, 12, 31)),
+            Some(date(2027, 1, 1))
+        );
+        assert_eq!(
+            parse_date("thursday", date(2026, 12, 31)),
+            Some(date(2026, 12, 31))
+        );
+        assert_eq!(
+            parse_date("TODAY", date(2026, 12, 31)),
+            Some(date(2026, 12, 31))
+        );
+    }
+
+    #[test]
+    fn rejects_impossible_dates() {
+        assert_eq!(parse_date("2026-02-30", date(2026, 1, 1)), None);
+        assert_eq!(parse_date("next week", date(2026, 1, 1)), None);
+        assert_eq!(
+            parse_date("2026-06-30", date(2026, 1, 1)),
+            Some(date(2026, 6, 30))
+        );
+    }
+}
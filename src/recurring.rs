@@ -0,0 +1,354 @@
+use chrono::{Datelike, Days, Local, NaiveDate, Weekday};
+use egui::RichText;
+use serde::{Deserialize, Serialize};
+use std::{fs, path::Path, time::Duration};
+
+use crate::task::Task;
+
+pub const WEEKDAYS: [Weekday; 7] = [
+    Weekday::Mon,
+    Weekday::Tue,
+    Weekday::Wed,
+    Weekday::Thu,
+    Weekday::Fri,
+    Weekday::Sat,
+    Weekday::Sun,
+];
+
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+pub enum Recurrence {
+    Daily,
+    Weekdays,
+    Weekly(Vec<Weekday>),
+    EveryNDays(u32),
+}
+
+impl Recurrence {
+    /// The first date strictly after `date` on which this rule occurs.
+    pub fn next_after(&self, date: NaiveDate) -> NaiveDate {
+        match self {
+            Recurrence::Daily => date + Days::new(1),
+            Recurrence::EveryNDays(n) => date + Days::new((*n).max(1) as u64),
+            Recurrence::Weekdays => {
+                Self::first_matching(date, |day| day != Weekday::Sat && day != Weekday::Sun)
+            }
+            Recurrence::Weekly(days) if days.is_empty() => date + Days::new(7),
+            Recurrence::Weekly(days) => Self::first_matching(date, |day| days.contains(&day)),
+        }
+    }
+
+    fn first_matching(date: NaiveDate, matches: impl Fn(Weekday) -> bool) -> NaiveDate {
+        (1..=7)
+            .map(|offset| date + Days::new(offset))
+            .find(|next| matches(next.weekday()))
+            .unwrap_or(date + Days::new(7))
+    }
+
+    pub fn label(&self) -> String {
+        match self {
+            Recurrence::Daily => "daily".to_string(),
+            Recurrence::Weekdays => "weekdays".to_string(),
+            Recurrence::Weekly(days) => {
+                let days: Vec<String> = days.iter().map(|day| day.to_string()).collect();
+                format!("weekly on {}", days.join(", "))
+            }
+            Recurrence::EveryNDays(n) => format!("every {} days", n),
+        }
+    }
+
+    /// Combo box plus the per-rule controls for editing a task's recurrence.
+    pub fn edit_ui(ui: &mut egui::Ui, id_salt: u64, recurrence: &mut Option<Recurrence>) {
+        let selected = match recurrence {
+            None => "Once",
+            Some(Recurrence::Daily) => "Daily",
+            Some(Recurrence::Weekdays) => "Weekdays",
+            Some(Recurrence::Weekly(_)) => "Weekly",
+            Some(Recurrence::EveryNDays(_)) => "Every N days",
+        };
+        egui::ComboBox::from_id_salt(("recurrence", id_salt))
+            .selected_text(selected)
+            .width(90.0)
+            .show_ui(ui, |ui| {
+                if ui.selectable_label(recurrence.is_none(), "Once").clicked() {
+                    *recurrence = None;
+                }
+                if ui.selectable_label(selected == "Daily", "Daily").clicked() {
+                    *recurrence = Some(Recurrence::Daily);
+                }
+                if ui
+                    .selectable_label(selected == "Weekdays", "Weekdays")
+                    .clicked()
+                {
+                    *recurrence = Some(Recurrence::Weekdays);
+                }
+                if ui
+                    .selectable_label(selected == "Weekly", "Weekly")
+                    .clicked()
+                    && selected != "Weekly"
+                {
+                    let today = Local::now().weekday();
+                    *recurrence = Some(Recurrence::Weekly(vec![today]));
+                }
+                if ui
+                    .selectable_label(selected == "Every N days", "Every N days")
+                    .clicked()
+                    && selected != "Every N days"
+                {
+                    *recurrence = Some(Recurrence::EveryNDays(2));
+                }
+            });
+
+        match recurrence {
+            Some(Recurrence::Weekly(days)) => weekday_toggles(ui, days),
+            Some(Recurrence::EveryNDays(n)) => {
+                ui.add(egui::DragValue::new(n).range(1..=365).suffix(" days"));
+            }
+            _ => {}
+        }
+    }
+}
+
+fn weekday_toggles(ui: &mut egui::Ui, days: &mut Vec<Weekday>) {
+    for day in WEEKDAYS {
+        let on = days.contains(&day);
+        if ui.selectable_label(on, day.to_string()).clicked() {
+            if on {
+                days.retain(|d| *d != day);
+            } else {
+                days.push(day);
+            }
+        }
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct TemplateTask {
+    pub name: String,
+    pub duration: Duration,
+    pub estimate: u32,
+    pub project: String,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct DayTemplate {
+    pub name: String,
+    pub weekdays: Vec<Weekday>, // empty means every day
+    pub tasks: Vec<TemplateTask>,
+}
+
+impl DayTemplate {
+    fn applies_on(&self, date: NaiveDate) -> bool {
+        self.weekdays.is_empty() || self.weekdays.contains(&date.weekday())
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct Templates {
+    templates: Vec<DayTemplate>,
+    last_applied: Option<NaiveDate>,
+    #[serde(skip)]
+    new_template_name: String,
+}
+
+impl Templates {
+    pub fn new() -> Self {
+        Self {
+            templates: Vec::new(),
+            last_applied: None,
+            new_template_name: String::new(),
+        }
+    }
+
+    /// Template tasks to add on the first launch of `today`, or nothing if already done.
+    pub fn due_for(&mut self, today: NaiveDate) -> Vec<TemplateTask> {
+        if self.last_applied == Some(today) {
+            return Vec::new();
+        }
+        self.last_applied = Some(today);
+        self.save();
+        self.templates
+            .iter()
+            .filter(|template| template.applies_on(today))
+            .flat_map(|template| template.tasks.clone())
+            .collect()
+    }
+
+    /// Manages templates. Returns template tasks the user asked to apply right away.
+    pub fn ui(&mut self, ui: &mut egui::Ui, current_tasks: &[Task]) -> Vec<TemplateTask> {
+        let mut apply = Vec::new();
+        let mut to_remove = None;
+        let mut changed = false;
+
+        for (i, template) in self.templates.iter_mut().enumerate() {
+            ui.horizontal(|ui| {
+                ui.label(RichText::new(&template.name).strong());
+                ui.label(RichText::new(format!("({} tasks)", template.tasks.len())).weak());
+                if ui.small_button("Apply now").clicked() {
+                    apply.extend(template.tasks.iter().cloned());
+                }
+                if ui.small_button("Delete").clicked() {
+                    to_remove = Some(i);
+                }
+            });
+            ui.horizontal(|ui| {
+                ui.label("Days:");
+                let before = template.weekdays.len();
+                weekday_toggles(ui, &mut template.weekdays);
+                changed |= before != template.weekdays.len();
+                if template.weekdays.is_empty() {
+                    ui.label(RichText::new("every day").weak());
+                }
+            });
+            ui.indent(("template", i), |ui| {
+                let mut task_to_remove = None;
+                for (j, task) in template.tasks.iter().enumerate() {
+                    ui.horizontal(|ui| {
+                        ui.label(format!(
+                            "{} ({} min)",
+                            task.name,
+                            task.duration.as_secs() / 60
+                        ));
+                        if ui.small_button("✖").clicked() {
+                            task_to_remove = Some(j);
+                        }
+                    });
+                }
+                if let Some(j) = task_to_remove {
+                    template.tasks.remove(j);
+                    changed = true;
+                }
+            });
+        }
+
+        if let Some(i) = to_remove {
+            self.templates.remove(i);
+            changed = true;
+        }
+
+        ui.horizontal(|ui| {
+            ui.add(
+                egui::TextEdit::singleline(&mut self.new_template_name)
+                    .hint_text("Template name")
+                    .desired_width(120.0),
+            );
+            if ui
+                .add_enabled(
+                    !current_tasks.is_empty() && !self.new_template_name.trim().is_empty(),
+                    egui::Button::new("Save current tasks as template"),
+                )
+                .clicked()
+            {
+                self.templates.push(DayTemplate {
+                    name: self.new_template_name.trim().to_string(),
+                    weekdays: Vec::new(),
+                    tasks: current_tasks
+                        .iter()
+                        .map(|task| TemplateTask {
+                            name: task.name.clone(),
+                            duration: task.duration,
+                            estimate: task.estimate,
+                            project: task.project.clone(),
+                        })
+                        .collect(),
+                });
+                self.new_template_name.clear();
+                changed = true;
+            }
+        });
+
+        if changed {
+            self.save();
+        }
+        apply
+    }
+
+    pub fn save(&self) {
+        if let Some(home_dir) = dirs::home_dir() {
+            let backup_dir = home_dir.join(".rust_pomodoro_backup");
+            fs::create_dir_all(&backup_dir).unwrap_or(());
+            let templates_json = serde_json::to_string(&self).unwrap_or_default();
+            fs::write(backup_dir.join("templates.json"), templates_json).unwrap_or(());
+        }
+    }
+
+    pub fn load() -> Self {
+        if let Some(home_dir) = dirs::home_dir() {
+            let file_path = home_dir.join(".rust_pomodoro_backup/templates.json");
+            if Path::new(&file_path).exists() {
+                if let Ok(contents) = fs::read_to_string(file_path) {
+                    if let Ok(templates) = serde_json::from_str(&contents) {
+                        return templates;
+                    }
+                }
+            }
+        }
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    #[test]
+    fn daily_rolls_over_month_year_and_leap_day() {
+        assert_eq!(
+            Recurrence::Daily.next_after(date(2026, 1, 31)),
+            date(2026, 2, 1)
+        );
+        assert_eq!(
+            Recurrence::Daily.next_after(date(2026, 12, 31)),
+            date(2027, 1, 1)
+        );
+        assert_eq!(
+            Recurrence::Daily.next_after(date(2028, 2, 28)),
+            date(2028, 2, 29)
+        );
+    }
+
+    #[test]
+    fn every_n_days_crosses_month_end_and_never_stands_still() {
+        let every_three = Recurrence::EveryNDays(3);
+        assert_eq!(every_three.next_after(date(2026, 2, 27)), date(2026, 3, 2));
+        assert_eq!(
+            Recurrence::EveryNDays(0).next_after(date(2026, 4, 30)),
+            date(2026, 5, 1)
+        );
+    }
+
+    #[test]
+    fn weekdays_skip_the_weekend() {
+        // Friday 2026-10-30 to Monday 2026-11-02
+        assert_eq!(
+            Recurrence::Weekdays.next_after(date(2026, 10, 30)),
+            date(2026, 11, 2)
+        );
+        assert_eq!(
+            Recurrence::Weekdays.next_after(date(2026, 11, 2)),
+            date(2026, 11, 3)
+        );
+    }
+
+    #[test]
+    fn weekly_picks_the_next_listed_day() {
+        let mon_wed = Recurrence::Weekly(vec![Weekday::Mon, Weekday::Wed]);
+        // Wednesday 2026-07-29 to Monday 2026-08-03
+        assert_eq!(mon_wed.next_after(date(2026, 7, 29)), date(2026, 8, 3));
+        assert_eq!(mon_wed.next_after(date(2026, 8, 3)), date(2026, 8, 5));
+
+        let same_day = Recurrence::Weekly(vec![Weekday::Wed]);
+        assert_eq!(same_day.next_after(date(2026, 7, 29)), date(2026, 8, 5));
+    }
+
+    #[test]
+    fn weekly_without_days_repeats_a_week_later() {
+        assert_eq!(
+            Recurrence::Weekly(Vec::new()).next_after(date(2026, 12, 28)),
+            date(2027, 1, 4)
+        );
+    }
+}
@@ -0,0 +1,288 @@
+use chrono::{DateTime, Local, NaiveDate, Timelike};
+use egui::{CollapsingHeader, Color32, RichText};
+use serde::{Deserialize, Serialize};
+use std::{fs, path::Path, time::Duration};
+
+use crate::theme;
+
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum Energy {
+    Low,
+    Medium,
+    High,
+}
+
+impl Energy {
+    const ALL: [Energy; 3] = [Energy::Low, Energy::Medium, Energy::High];
+
+    fn label(&self) -> &'static str {
+        match self {
+            Energy::Low => "Low",
+            Energy::Medium => "Medium",
+            Energy::High => "High",
+        }
+    }
+
+    fn color(&self) -> Color32 {
+        let palette = theme::palette();
+        match self {
+            Energy::Low => palette.danger,
+            Energy::Medium => palette.warning,
+            Energy::High => palette.success,
+        }
+    }
+}
+
+/// What the user noted at the end of a work session.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Reflection {
+    pub at: DateTime<Local>, // when the session ended
+    pub length: Duration,
+    pub task_id: Option<u64>,
+    pub task: Option<String>,
+    pub note: String,
+    pub focus: u8, // 1-5
+    pub energy: Energy,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct Journal {
+    pub enabled: bool, // ask after every work session
+    entries: Vec<Reflection>,
+    #[serde(skip)]
+    draft: Option<Reflection>, // session waiting for the prompt to be filled in
+    #[serde(skip)]
+    search: String,
+}
+
+impl Journal {
+    pub fn new() -> Self {
+        Self {
+            enabled: true,
+            entries: Vec::new(),
+            draft: None,
+            search: String::new(),
+        }
+    }
+
+    /// Opens the prompt for a work session that just ended, if prompting is on.
+    pub fn prompt(&mut self, length: Duration, task: Option<(u64, String)>) {
+        if !self.enabled {
+            return;
+        }
+        let (task_id, task) = task.unzip();
+        self.draft = Some(Reflection {
+            at: Local::now(),
+            length,
+            task_id,
+            task,
+            note: String::new(),
+            focus: 3,
+            energy: Energy::Medium,
+        });
+    }
+
+    /// The end-of-session prompt, shown over whichever tab is open.
+    pub fn prompt_ui(&mut self, ctx: &egui::Context) {
+        let Some(draft) = self.draft.as_mut() else {
+            return;
+        };
+
+        let mut save = false;
+        let mut skip = false;
+        egui::Window::new("How did that pomodoro go?")
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+            .show(ctx, |ui| {
+                if let Some(task) = &draft.task {
+                    ui.label(RichText::new(task).strong());
+                }
+                ui.add(
+                    egui::TextEdit::multiline(&mut draft.note)
+                        .hint_text("What did you get done?")
+                        .desired_rows(3),
+                );
+                ui.horizontal(|ui| {
+                    ui.label("Focus:");
+                    for focus in 1..=5 {
+                        ui.selectable_value(&mut draft.focus, focus, focus.to_string());
+                    }
+                });
+                ui.horizontal(|ui| {
+                    ui.label("Energy:");
+                    for energy in Energy::ALL {
+                        ui.selectable_value(&mut draft.energy, energy, energy.label());
+                    }
+                });
+                ui.add_space(5.0);
+                ui.horizontal(|ui| {
+                    save = ui.button("Save").clicked();
+                    skip = ui.button("Skip").clicked();
+                    if ui
+                        .checkbox(&mut self.enabled, "Ask after each pomodoro")
+                        .changed()
+                    {
+                        skip |= !self.enabled;
+                    }
+                });
+            });
+
+        if save {
+            if let Some(reflection) = self.draft.take() {
+                self.entries.push(reflection);
+            }
+            self.save();
+        } else if skip {
+            self.draft = None;
+            self.save();
+        }
+    }
+
+    fn matches(&self, entry: &Reflection) -> bool {
+        let needle = self.search.trim().to_lowercase();
+        needle.is_empty()
+            || entry.note.to_lowercase().contains(&needle)
+            || entry
+                .task
+                .as_ref()
+                .is_some_and(|task| task.to_lowercase().contains(&needle))
+    }
+
+    pub fn ui(&mut self, ui: &mut egui::Ui) {
+        ui.vertical_centered(|ui| {
+            ui.add_space(10.0);
+            ui.heading("Journal");
+            ui.add_space(10.0);
+        });
+
+        ui.horizontal(|ui| {
+            ui.add(
+                egui::TextEdit::singleline(&mut self.search)
+                    .hint_text("Search notes and tasks")
+                    .desired_width(200.0),
+            );
+            if ui
+                .checkbox(&mut self.enabled, "Ask after each pomodoro")
+                .changed()
+            {
+                self.save();
+            }
+        });
+        ui.add_space(10.0);
+
+        let entries: Vec<&Reflection> = self
+            .entries
+            .iter()
+            .rev()
+            .filter(|entry| self.matches(entry))
+            .collect();
+        if entries.is_empty() {
+            ui.label(RichText::new("No reflections yet").weak());
+            return;
+        }
+
+        egui::ScrollArea::vertical().show(ui, |ui| {
+            ui.collapsing("Focus by hour of day", |ui| {
+                focus_by_hour_ui(ui, &entries);
+            });
+            ui.add_space(5.0);
+
+            // Newest day first, one summary per day
+            let mut days: Vec<(NaiveDate, Vec<&Reflection>)> = Vec::new();
+            for entry in entries {
+                let date = entry.at.date_naive();
+                match days.last_mut() {
+                    Some((last, items)) if *last == date => items.push(entry),
+                    _ => days.push((date, vec![entry])),
+                }
+            }
+
+            for (date, items) in &days {
+                let focus =
+                    items.iter().map(|entry| entry.focus as f32).sum::<f32>() / items.len() as f32;
+                let minutes: u64 = items.iter().map(|entry| entry.length.as_secs() / 60).sum();
+                CollapsingHeader::new(format!(
+                    "{} — {} pomodoros, {}h {}m, focus {:.1}",
+                    date.format("%A, %B %d"),
+                    items.len(),
+                    minutes / 60,
+                    minutes % 60,
+                    focus
+                ))
+                .id_salt(("journal_day", *date))
+                .default_open(days.len() == 1)
+                .show(ui, |ui| {
+                    for entry in items {
+                        ui.horizontal_wrapped(|ui| {
+                            ui.label(RichText::new(entry.at.format("%H:%M").to_string()).weak());
+                            if let Some(task) = &entry.task {
+                                ui.label(RichText::new(task).strong());
+                            }
+                            ui.label(format!("focus {}/5", entry.focus));
+                            ui.label(
+                                RichText::new(format!("{} energy", entry.energy.label()))
+                                    .color(entry.energy.color()),
+                            );
+                        });
+                        if !entry.note.is_empty() {
+                            ui.label(&entry.note);
+                        }
+                        ui.add_space(4.0);
+                    }
+                });
+            }
+        });
+    }
+
+    pub fn save(&self) {
+        if let Some(home_dir) = dirs::home_dir() {
+            let backup_dir = home_dir.join(".rust_pomodoro_backup");
+            fs::create_dir_all(&backup_dir).unwrap_or(());
+            let journal_json = serde_json::to_string(&self).unwrap_or_default();
+            fs::write(backup_dir.join("journal.json"), journal_json).unwrap_or(());
+        }
+    }
+
+    pub fn load() -> Self {
+        if let Some(home_dir) = dirs::home_dir() {
+            let file_path = home_dir.join(".rust_pomodoro_backup/journal.json");
+            if Path::new(&file_path).exists() {
+                if let Ok(contents) = fs::read_to_string(file_path) {
+                    if let Ok(journal) = serde_json::from_str(&contents) {
+                        return journal;
+                    }
+                }
+            }
+        }
+        Self::new()
+    }
+}
+
+/// Average focus rating per hour the sessions ended in.
+fn focus_by_hour_ui(ui: &mut egui::Ui, entries: &[&Reflection]) {
+    let mut hours = [(0u32, 0u32); 24]; // (sum of ratings, sessions)
+    for entry in entries {
+        let hour = &mut hours[entry.at.hour() as usize];
+        hour.0 += entry.focus as u32;
+        hour.1 += 1;
+    }
+
+    egui::Grid::new("focus_by_hour")
+        .spacing([10.0, 4.0])
+        .show(ui, |ui| {
+            for (hour, (sum, count)) in hours.iter().enumerate() {
+                if *count == 0 {
+                    continue;
+                }
+                let average = *sum as f32 / *count as f32;
+                ui.label(format!("{:02}:00", hour));
+                ui.add(
+                    egui::ProgressBar::new(average / 5.0)
+                        .desired_width(200.0)
+                        .text(format!("{:.1} ({} sessions)", average, count)),
+                );
+                ui.end_row();
+            }
+        });
+}
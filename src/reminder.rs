@@ -0,0 +1,106 @@
+use egui::{Align2, RichText};
+use serde::{Deserialize, Serialize};
+use std::{fs, path::Path};
+
+use crate::sound::{self, SoundEvent};
+use crate::theme;
+
+/// A reminder that has fired and is waiting to be dismissed.
+pub struct Notice {
+    pub task_id: u64,
+    pub title: String,
+    pub message: String,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct Reminders {
+    pub lead_minutes: u32, // how long before the task's time to remind
+    #[serde(skip)]
+    notices: Vec<Notice>,
+}
+
+impl Reminders {
+    pub fn new() -> Self {
+        Self {
+            lead_minutes: 5,
+            notices: Vec::new(),
+        }
+    }
+
+    pub fn notify(&mut self, notice: Notice) {
+        self.notices.retain(|other| other.task_id != notice.task_id);
+        self.notices.push(notice);
+        sound::play(SoundEvent::TaskDue);
+    }
+
+    /// Lead time setting, shown alongside the other day settings.
+    pub fn settings_ui(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            ui.label("Remind me");
+            if ui
+                .add(
+                    egui::DragValue::new(&mut self.lead_minutes)
+                        .range(0..=120)
+                        .suffix(" min"),
+                )
+                .changed()
+            {
+                self.save();
+            }
+            ui.label("before a task's time");
+        });
+    }
+
+    /// Stacks the pending notices in the top right corner, whichever tab is open.
+    pub fn ui(&mut self, ctx: &egui::Context) {
+        let mut to_dismiss = None;
+        for (i, notice) in self.notices.iter().enumerate() {
+            egui::Area::new(egui::Id::new(("reminder", notice.task_id)))
+                .anchor(Align2::RIGHT_TOP, [-10.0, 40.0 + i as f32 * 70.0])
+                .show(ctx, |ui| {
+                    egui::Frame::popup(ui.style()).show(ui, |ui| {
+                        ui.set_width(240.0);
+                        ui.horizontal(|ui| {
+                            ui.label(
+                                RichText::new(format!("⏰ {}", notice.title))
+                                    .strong()
+                                    .color(theme::palette().warning),
+                            );
+                            ui.with_layout(egui::Layout::right_to_left(egui::Align::Min), |ui| {
+                                if ui.small_button("✖").clicked() {
+                                    to_dismiss = Some(i);
+                                }
+                            });
+                        });
+                        ui.label(&notice.message);
+                    });
+                });
+        }
+        if let Some(index) = to_dismiss {
+            self.notices.remove(index);
+        }
+    }
+
+    pub fn save(&self) {
+        if let Some(home_dir) = dirs::home_dir() {
+            let backup_dir = home_dir.join(".rust_pomodoro_backup");
+            fs::create_dir_all(&backup_dir).unwrap_or(());
+            let reminders_json = serde_json::to_string(&self).unwrap_or_default();
+            fs::write(backup_dir.join("reminders.json"), reminders_json).unwrap_or(());
+        }
+    }
+
+    pub fn load() -> Self {
+        if let Some(home_dir) = dirs::home_dir() {
+            let file_path = home_dir.join(".rust_pomodoro_backup/reminders.json");
+            if Path::new(&file_path).exists() {
+                if let Ok(contents) = fs::read_to_string(file_path) {
+                    if let Ok(reminders) = serde_json::from_str(&contents) {
+                        return reminders;
+                    }
+                }
+            }
+        }
+        Self::new()
+    }
+}
@@ -0,0 +1,76 @@
+use chrono::{DateTime, Local, NaiveDate, NaiveTime, TimeDelta};
+use serde::{Deserialize, Serialize};
+use std::{fs, path::Path};
+
+/// Tracks which working day it is. A day starts at `day_start_hour` rather than midnight,
+/// so a late session still counts towards the day it began.
+#[derive(Serialize, Deserialize)]
+pub struct Rollover {
+    pub day_start_hour: u32,
+    last_day: Option<NaiveDate>,
+}
+
+impl Rollover {
+    pub fn new() -> Self {
+        Self {
+            day_start_hour: 4,
+            last_day: None,
+        }
+    }
+
+    pub fn today(&self) -> NaiveDate {
+        self.day_of(Local::now())
+    }
+
+    /// The working day a moment belongs to.
+    pub fn day_of(&self, at: DateTime<Local>) -> NaiveDate {
+        (at - TimeDelta::hours(self.day_start_hour as i64)).date_naive()
+    }
+
+    /// Time of day at which a new working day begins.
+    pub fn day_start(&self) -> NaiveTime {
+        NaiveTime::from_hms_opt(self.day_start_hour, 0, 0).unwrap_or(NaiveTime::MIN)
+    }
+
+    /// Number of days since the last recorded day, if a new one has started.
+    pub fn check(&mut self) -> Option<u32> {
+        let today = self.today();
+        match self.last_day {
+            Some(last) if last < today => {
+                self.last_day = Some(today);
+                self.save();
+                Some((today - last).num_days() as u32)
+            }
+            // Same day, or the clock went back: keep the later day so it isn't counted twice
+            Some(_) => None,
+            None => {
+                self.last_day = Some(today);
+                self.save(); // first launch ever, nothing to carry over
+                None
+            }
+        }
+    }
+
+    pub fn save(&self) {
+        if let Some(home_dir) = dirs::home_dir() {
+            let backup_dir = home_dir.join(".rust_pomodoro_backup");
+            fs::create_dir_all(&backup_dir).unwrap_or(());
+            let rollover_json = serde_json::to_string(&self).unwrap_or_default();
+            fs::write(backup_dir.join("rollover.json"), rollover_json).unwrap_or(());
+        }
+    }
+
+    pub fn load() -> Self {
+        if let Some(home_dir) = dirs::home_dir() {
+            let file_path = home_dir.join(".rust_pomodoro_backup/rollover.json");
+            if Path::new(&file_path).exists() {
+                if let Ok(contents) = fs::read_to_string(file_path) {
+                    if let Ok(rollover) = serde_json::from_str(&contents) {
+                        return rollover;
+                    }
+                }
+            }
+        }
+        Self::new()
+    }
+}
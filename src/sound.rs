@@ -1,38 +1,436 @@
-use rodio::{OutputStream, Sink, Source};
+use chrono::Local;
+use rodio::cpal::traits::HostTrait;
+use rodio::{Decoder, Device, DeviceTrait, OutputStream, OutputStreamHandle, Sink};
+use serde::{Deserialize, Serialize};
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    mpsc::{self, Receiver, RecvTimeoutError, Sender},
+    Mutex, OnceLock,
+};
+use std::time::{Duration, Instant};
+use std::{fs::File, io::BufReader, path::PathBuf, thread};
 
-pub fn play_notification() {
-    // Simple notification sound using a basic sine wave
-    if let Ok((_stream, stream_handle)) = OutputStream::try_default() {
-        let sink = Sink::try_new(&stream_handle).unwrap();
+use crate::ambient::{Ambient, Generator};
+use crate::music;
+use crate::sound_settings::SoundSettings;
+use crate::tone;
 
-        let source = rodio::source::SineWave::new(440.0) // A4 note
-            .take_duration(std::time::Duration::from_secs_f32(0.25))
-            .amplify(0.20);
+/// Moments the app makes a sound for. Each can be given its own file and volume.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum SoundEvent {
+    WorkStart,
+    ShortBreak,
+    LongBreak,
+    TimerFinished,
+    TaskDue,
+}
+
+impl SoundEvent {
+    pub const ALL: [SoundEvent; 5] = [
+        SoundEvent::WorkStart,
+        SoundEvent::ShortBreak,
+        SoundEvent::LongBreak,
+        SoundEvent::TimerFinished,
+        SoundEvent::TaskDue,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            SoundEvent::WorkStart => "Work start",
+            SoundEvent::ShortBreak => "Short break",
+            SoundEvent::LongBreak => "Long break",
+            SoundEvent::TimerFinished => "Timer finished",
+            SoundEvent::TaskDue => "Task due",
+        }
+    }
+
+    /// Name of the built-in tone the event plays by default.
+    fn builtin(&self) -> &'static str {
+        match self {
+            SoundEvent::WorkStart | SoundEvent::ShortBreak | SoundEvent::LongBreak => {
+                "Notification"
+            }
+            SoundEvent::TimerFinished => "Alarm",
+            SoundEvent::TaskDue => "Reminder",
+        }
+    }
+}
+
+enum Command {
+    Play(SoundEvent, f32), // event and a gain on top of its volume
+    Configure(Box<SoundSettings>),
+    Ambient(bool), // whether the background layer should be audible
+    Skip(i32),     // tracks to move forward, or back when negative
+}
+
+const AMBIENT_FADE: Duration = Duration::from_secs(2);
+const DEVICE_CHECK_INTERVAL: Duration = Duration::from_secs(5);
+
+static AUDIO: OnceLock<Option<Sender<Command>>> = OnceLock::new();
+static NOW_PLAYING: Mutex<Option<String>> = Mutex::new(None);
+
+fn send(command: Command) {
+    let sender = AUDIO.get_or_init(|| {
+        let (sender, receiver) = mpsc::channel();
+        thread::Builder::new()
+            .name("audio".to_string())
+            .spawn(move || run(receiver))
+            .ok()
+            .map(|_| sender)
+    });
+    if let Some(sender) = sender {
+        sender.send(command).unwrap_or(());
+    }
+}
+
+/// Queues the sound for `event` on the audio thread. Never blocks; does nothing without
+/// an audio device.
+pub fn play(event: SoundEvent) {
+    send(Command::Play(event, 1.0));
+}
+
+/// Like `play`, scaled by `gain` (0 to 1), for alarms that start soft and get louder.
+pub fn play_at(event: SoundEvent, gain: f32) {
+    send(Command::Play(event, gain.clamp(0.0, 1.0)));
+}
+
+/// Fades the background sound in or out. Cheap to call every frame, only changes are sent.
+pub fn set_ambient(active: bool) {
+    static ACTIVE: AtomicBool = AtomicBool::new(false);
+    if ACTIVE.swap(active, Ordering::Relaxed) != active {
+        send(Command::Ambient(active));
+    }
+}
+
+/// Moves the focus music `offset` tracks forward, or back when negative.
+pub fn skip_track(offset: i32) {
+    send(Command::Skip(offset));
+}
+
+/// Name of the focus music track that is playing or paused.
+pub fn now_playing() -> Option<String> {
+    NOW_PLAYING.lock().ok()?.clone()
+}
+
+/// Names of the output devices currently available.
+pub fn output_devices() -> Vec<String> {
+    rodio::cpal::default_host()
+        .output_devices()
+        .map(|devices| devices.filter_map(|device| device.name().ok()).collect())
+        .unwrap_or_default()
+}
+
+fn find_device(name: &str) -> Option<Device> {
+    rodio::cpal::default_host()
+        .output_devices()
+        .ok()?
+        .find(|device| device.name().is_ok_and(|other| other == name))
+}
+
+/// Replaces the files and volumes the audio thread plays with.
+pub fn configure(settings: SoundSettings) {
+    send(Command::Configure(Box::new(settings)));
+}
 
+/// Plays the event's file, falling back to its tone if the file is missing or cannot be
+/// decoded, and to the built-in tone if the chosen one is gone.
+fn append_event(sink: &Sink, event: SoundEvent, gain: f32, settings: &SoundSettings) {
+    let sound = settings.event(event);
+    sink.set_volume(settings.master_volume * sound.volume * gain);
+
+    let path = sound.file.trim();
+    if !path.is_empty() {
+        if let Some(decoder) = File::open(path)
+            .ok()
+            .and_then(|file| Decoder::new(BufReader::new(file)).ok())
+        {
+            sink.append(decoder);
+            return;
+        }
+    }
+
+    let name = match sound.tone.as_str() {
+        "" => event.builtin(),
+        name => name,
+    };
+    let presets = tone::presets();
+    let find = |name: &str| {
+        settings
+            .tones
+            .iter()
+            .chain(&presets)
+            .find(|sequence| sequence.name == name)
+            .and_then(|sequence| sequence.source())
+    };
+    let source = find(name).or_else(|| find(event.builtin()));
+    if let Some(source) = source {
         sink.append(source);
-        sink.sleep_until_end();
     }
 }
 
-pub fn play_alarm() {
-    // More prominent alarm sound
-    if let Ok((_stream, stream_handle)) = OutputStream::try_default() {
-        let sink = Sink::try_new(&stream_handle).unwrap();
+/// The background layer and how far it is faded in (0 to 1).
+struct AmbientLayer {
+    sink: Sink,
+    kind: Ambient,
+    file: String,
+    gain: f32,
+}
+
+/// The focus music playlist. The sink holds the current track, paused between work phases
+/// so it picks up where it stopped.
+struct MusicPlayer {
+    source: String,
+    tracks: Vec<PathBuf>,
+    current: usize,
+    sink: Option<Sink>,
+}
+
+impl MusicPlayer {
+    fn new(source: &str) -> Self {
+        Self {
+            source: source.to_string(),
+            tracks: music::load(source).unwrap_or_default(),
+            current: 0,
+            sink: None,
+        }
+    }
+
+    fn skip(&mut self, offset: i32) {
+        if self.tracks.is_empty() {
+            return;
+        }
+        let count = self.tracks.len() as i64;
+        self.current = (self.current as i64 + offset as i64).rem_euclid(count) as usize;
+        self.sink = None;
+        self.publish();
+    }
+
+    fn publish(&self) {
+        if let Ok(mut now_playing) = NOW_PLAYING.lock() {
+            *now_playing = self
+                .tracks
+                .get(self.current)
+                .map(|path| music::track_name(path));
+        }
+    }
+}
+
+/// State owned by the audio thread. The output stream lives here for the life of the app
+/// (it cannot be sent between threads) and every sound gets its own sink, so sounds overlap
+/// freely.
+struct Engine {
+    output: Option<(OutputStream, OutputStreamHandle)>,
+    output_device: Option<String>, // the chosen device the stream is open on, None for default
+    devices_checked: Option<Instant>, // when the device list was last looked through
+    sinks: Vec<Sink>,
+    settings: SoundSettings,
+    ambient: Option<AmbientLayer>,
+    ambient_active: bool, // a work phase is running; gates the focus music too
+    music: MusicPlayer,
+}
 
-        // Two-tone alarm
-        let source1 = rodio::source::SineWave::new(880.0) // Higher pitch
-            .take_duration(std::time::Duration::from_secs_f32(0.3))
-            .amplify(0.25);
+impl Engine {
+    /// The stream to play on. Follows the chosen device, falling back to the default one
+    /// while it is missing and moving back once it reappears.
+    fn handle(&mut self) -> Option<&OutputStreamHandle> {
+        let wanted = self.settings.output_device.trim().to_string();
+        // Listing devices is slow, so an open stream is only checked against them now and then
+        let check = self
+            .devices_checked
+            .map_or(true, |checked| checked.elapsed() >= DEVICE_CHECK_INTERVAL);
+        let stale = match &self.output_device {
+            Some(name) => *name != wanted || (check && find_device(name).is_none()),
+            None => !wanted.is_empty() && check && find_device(&wanted).is_some(),
+        };
+        if check {
+            self.devices_checked = Some(Instant::now());
+        }
 
-        let source2 = rodio::source::SineWave::new(660.0) // Lower pitch
-            .take_duration(std::time::Duration::from_secs_f32(0.3))
-            .amplify(0.25);
+        // Try again every time, a device may have been plugged in since
+        if self.output.is_none() || stale {
+            self.sinks.clear();
+            self.ambient = None;
+            self.music.sink = None;
+            let device = find_device(&wanted).filter(|_| !wanted.is_empty());
+            self.output = device
+                .as_ref()
+                .and_then(|device| OutputStream::try_from_device(device).ok());
+            self.output_device = self.output.as_ref().map(|_| wanted);
+            if self.output.is_none() {
+                self.output = OutputStream::try_default().ok();
+            }
+        }
+        self.output.as_ref().map(|(_, handle)| handle)
+    }
+
+    /// Muted, or inside quiet hours.
+    fn silenced(&self) -> bool {
+        self.settings.is_quiet(Local::now().time())
+    }
+
+    fn play(&mut self, event: SoundEvent, gain: f32) {
+        if self.silenced() {
+            return;
+        }
+        let Some(handle) = self.handle() else {
+            return;
+        };
+        match Sink::try_new(handle) {
+            Ok(sink) => {
+                append_event(&sink, event, gain, &self.settings);
+                self.sinks.retain(|sink| !sink.empty());
+                self.sinks.push(sink);
+            }
+            // The device may have gone away; look again before the next sound
+            Err(_) => self.devices_checked = None,
+        }
+    }
+
+    fn configure(&mut self, settings: SoundSettings) {
+        // A different sound means a new layer, picked up again by the next fade step
+        if self.ambient.as_ref().is_some_and(|layer| {
+            layer.kind != settings.ambient || layer.file != settings.ambient_file
+        }) {
+            self.ambient = None;
+        }
+        if settings.output_device != self.settings.output_device {
+            self.devices_checked = None; // switch on the next sound, not the next check
+        }
+        if self.music.source != settings.music_source {
+            self.music = MusicPlayer::new(&settings.music_source);
+            self.music.publish();
+        }
+        if let Some(sink) = &self.music.sink {
+            sink.set_volume(settings.master_volume * settings.music_volume);
+        }
+        self.settings = settings;
+    }
+
+    fn start_ambient(&mut self) -> Option<AmbientLayer> {
+        let kind = self.settings.ambient;
+        let file = self.settings.ambient_file.clone();
+        let sink = Sink::try_new(self.handle()?).ok()?;
+        match Generator::new(kind) {
+            Some(generator) => sink.append(generator),
+            None => {
+                let file = File::open(file.trim()).ok()?;
+                sink.append(Decoder::new_looped(BufReader::new(file)).ok()?);
+            }
+        }
+        sink.set_volume(0.0);
+        Some(AmbientLayer {
+            sink,
+            kind,
+            file,
+            gain: 0.0,
+        })
+    }
+
+    /// Moves the background layer one step towards its target. Returns true while fading.
+    fn fade_ambient(&mut self, elapsed: Duration) -> bool {
+        let wanted =
+            self.ambient_active && self.settings.ambient != Ambient::Off && !self.silenced();
+        if wanted && self.ambient.is_none() {
+            self.ambient = self.start_ambient();
+        }
+        let Some(layer) = &mut self.ambient else {
+            return false;
+        };
+
+        let target = if wanted { 1.0 } else { 0.0 };
+        let step = elapsed.as_secs_f32() / AMBIENT_FADE.as_secs_f32();
+        layer.gain = if layer.gain < target {
+            (layer.gain + step).min(target)
+        } else {
+            (layer.gain - step).max(target)
+        };
+        layer
+            .sink
+            .set_volume(self.settings.master_volume * self.settings.ambient_volume * layer.gain);
+        if layer.gain == 0.0 {
+            layer.sink.pause(); // resumes where it left off next work phase
+        } else {
+            layer.sink.play();
+        }
+        layer.gain != target
+    }
+
+    /// Plays the focus music during work phases and pauses it otherwise, moving on to the
+    /// next track when one ends.
+    fn step_music(&mut self) {
+        let wanted = self.ambient_active
+            && self.settings.music
+            && !self.music.tracks.is_empty()
+            && !self.silenced();
+        if !wanted {
+            if let Some(sink) = &self.music.sink {
+                sink.pause();
+            }
+            return;
+        }
+
+        if self.music.sink.as_ref().is_some_and(|sink| sink.empty()) {
+            self.music.skip(1);
+        }
+        // Skip past files that cannot be decoded, but only once round the list
+        for _ in 0..self.music.tracks.len() {
+            if self.music.sink.is_some() {
+                break;
+            }
+            let Some(handle) = self.handle() else {
+                return;
+            };
+            let Ok(sink) = Sink::try_new(handle) else {
+                return;
+            };
+            match File::open(&self.music.tracks[self.music.current])
+                .ok()
+                .and_then(|file| Decoder::new(BufReader::new(file)).ok())
+            {
+                Some(decoder) => {
+                    sink.append(decoder);
+                    sink.set_volume(self.settings.master_volume * self.settings.music_volume);
+                    self.music.sink = Some(sink);
+                }
+                None => self.music.skip(1),
+            }
+        }
+        if let Some(sink) = &self.music.sink {
+            sink.play();
+        }
+    }
+}
 
-        sink.append(source1.clone());
-        sink.append(source2.clone());
-        sink.append(source1);
-        sink.append(source2);
+fn run(receiver: Receiver<Command>) {
+    let mut engine = Engine {
+        output: None,
+        output_device: None,
+        devices_checked: None,
+        sinks: Vec::new(),
+        settings: SoundSettings::new(),
+        ambient: None,
+        ambient_active: false,
+        music: MusicPlayer::new(""),
+    };
+    let mut fading = false;
+    let mut last_step = Instant::now();
 
-        sink.sleep_until_end();
+    loop {
+        let wait = if fading {
+            Duration::from_millis(20)
+        } else {
+            Duration::from_secs(1)
+        };
+        match receiver.recv_timeout(wait) {
+            Ok(Command::Play(event, gain)) => engine.play(event, gain),
+            Ok(Command::Configure(settings)) => engine.configure(*settings),
+            Ok(Command::Ambient(active)) => engine.ambient_active = active,
+            Ok(Command::Skip(offset)) => engine.music.skip(offset),
+            Err(RecvTimeoutError::Timeout) => {}
+            Err(RecvTimeoutError::Disconnected) => break,
+        }
+        fading = engine.fade_ambient(last_step.elapsed());
+        engine.step_music();
+        last_step = Instant::now();
     }
 }
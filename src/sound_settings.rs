@@ -0,0 +1,427 @@
+use chrono::NaiveTime;
+use egui::RichText;
+use serde::{Deserialize, Serialize};
+use std::{collections::HashMap, fs, io::BufReader, path::Path};
+
+use crate::ambient::Ambient;
+use crate::music;
+use crate::sound::{self, SoundEvent};
+use crate::theme;
+use crate::tone::{self, ToneSequence};
+
+/// What plays for one event: a sound file, or a synthesized tone when `file` is empty.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct EventSound {
+    pub file: String,
+    #[serde(default)]
+    pub tone: String, // name of a tone sequence, empty for the event's built-in tone
+    pub volume: f32,
+}
+
+impl Default for EventSound {
+    fn default() -> Self {
+        Self {
+            file: String::new(),
+            tone: String::new(),
+            volume: 1.0,
+        }
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct SoundSettings {
+    pub master_volume: f32,
+    pub events: HashMap<SoundEvent, EventSound>,
+    #[serde(default)]
+    pub ambient: Ambient, // background sound during work phases
+    #[serde(default)]
+    pub ambient_file: String,
+    #[serde(default = "default_ambient_volume")]
+    pub ambient_volume: f32,
+    #[serde(default)]
+    pub output_device: String, // empty for the system default
+    #[serde(default)]
+    pub muted: bool,
+    #[serde(default)]
+    pub quiet_hours: bool,
+    #[serde(default = "default_quiet_start")]
+    pub quiet_start: (u32, u32), // (hour, minute)
+    #[serde(default = "default_quiet_end")]
+    pub quiet_end: (u32, u32),
+    #[serde(default)]
+    pub music: bool, // play the playlist during work phases
+    #[serde(default)]
+    pub music_source: String, // a folder of audio files or an .m3u playlist
+    #[serde(default = "default_music_volume")]
+    pub music_volume: f32,
+    #[serde(skip)]
+    problems: HashMap<SoundEvent, String>, // files that will fall back to the built-in tone
+    #[serde(skip)]
+    pub tones: Vec<ToneSequence>, // presets and the user's tones.json
+    #[serde(skip)]
+    tone_errors: Vec<String>,
+    #[serde(skip)]
+    ambient_problem: Option<String>,
+    #[serde(skip)]
+    devices: Vec<String>, // output devices found when the settings were loaded or refreshed
+    #[serde(skip)]
+    music_tracks: Option<Result<usize, String>>, // track count, or why there are none
+}
+
+fn default_ambient_volume() -> f32 {
+    0.4
+}
+
+fn default_music_volume() -> f32 {
+    0.5
+}
+
+fn default_quiet_start() -> (u32, u32) {
+    (22, 0)
+}
+
+fn default_quiet_end() -> (u32, u32) {
+    (7, 0)
+}
+
+impl SoundSettings {
+    pub fn new() -> Self {
+        Self {
+            master_volume: 0.8,
+            events: HashMap::new(),
+            ambient: Ambient::Off,
+            ambient_file: String::new(),
+            ambient_volume: default_ambient_volume(),
+            output_device: String::new(),
+            muted: false,
+            quiet_hours: false,
+            quiet_start: default_quiet_start(),
+            quiet_end: default_quiet_end(),
+            music: false,
+            music_source: String::new(),
+            music_volume: default_music_volume(),
+            problems: HashMap::new(),
+            tones: tone::presets(),
+            tone_errors: Vec::new(),
+            ambient_problem: None,
+            devices: Vec::new(),
+            music_tracks: None,
+        }
+    }
+
+    pub fn event(&self, event: SoundEvent) -> EventSound {
+        self.events.get(&event).cloned().unwrap_or_default()
+    }
+
+    /// Whether sounds are off at `time`, by the mute switch or quiet hours.
+    pub fn is_quiet(&self, time: NaiveTime) -> bool {
+        if self.muted {
+            return true;
+        }
+        if !self.quiet_hours {
+            return false;
+        }
+        let at = |(hour, minute): (u32, u32)| {
+            NaiveTime::from_hms_opt(hour, minute, 0).unwrap_or(NaiveTime::MIN)
+        };
+        let (start, end) = (at(self.quiet_start), at(self.quiet_end));
+        if start <= end {
+            start <= time && time < end
+        } else {
+            time >= start || time < end // runs past midnight
+        }
+    }
+
+    /// Flips the mute switch and tells the audio thread.
+    pub fn toggle_mute(&mut self) {
+        self.muted = !self.muted;
+        self.apply();
+        self.save();
+    }
+
+    /// Hands the settings to the audio thread.
+    pub fn apply(&self) {
+        sound::configure(self.clone());
+    }
+
+    fn check_files(&mut self) {
+        self.problems = self
+            .events
+            .iter()
+            .filter(|(_, sound)| !sound.file.trim().is_empty())
+            .filter_map(|(event, sound)| check_file(sound.file.trim()).err().map(|e| (*event, e)))
+            .collect();
+        self.ambient_problem = match self.ambient {
+            Ambient::File => check_file(self.ambient_file.trim()).err(),
+            _ => None,
+        };
+        self.music_tracks = match self.music_source.trim() {
+            "" => None,
+            source => Some(music::load(source).map(|tracks| tracks.len())),
+        };
+    }
+
+    fn load_tones(&mut self) {
+        (self.tones, self.tone_errors) = tone::load();
+    }
+
+    pub fn ui(&mut self, ui: &mut egui::Ui) {
+        ui.heading("Sounds");
+        ui.add_space(5.0);
+
+        let mut changed = false;
+        ui.horizontal(|ui| {
+            ui.label("Output device:");
+            egui::ComboBox::from_id_salt("output_device")
+                .selected_text(if self.output_device.is_empty() {
+                    "System default"
+                } else {
+                    self.output_device.as_str()
+                })
+                .width(220.0)
+                .show_ui(ui, |ui| {
+                    changed |= ui
+                        .selectable_value(&mut self.output_device, String::new(), "System default")
+                        .changed();
+                    for name in &self.devices {
+                        changed |= ui
+                            .selectable_value(&mut self.output_device, name.clone(), name)
+                            .changed();
+                    }
+                });
+            if ui
+                .small_button("↻")
+                .on_hover_text("Look for devices")
+                .clicked()
+            {
+                self.devices = sound::output_devices();
+            }
+        });
+        if !self.output_device.is_empty() && !self.devices.contains(&self.output_device) {
+            ui.label(
+                RichText::new("⚠ Device not connected, using the system default")
+                    .small()
+                    .color(theme::palette().warning),
+            );
+        }
+        ui.horizontal(|ui| {
+            changed |= ui.checkbox(&mut self.muted, "Mute all sounds").changed();
+        });
+        ui.horizontal(|ui| {
+            changed |= ui
+                .checkbox(&mut self.quiet_hours, "Quiet hours from")
+                .changed();
+            changed |= time_input(ui, &mut self.quiet_start);
+            ui.label("to");
+            changed |= time_input(ui, &mut self.quiet_end);
+        });
+        ui.label(RichText::new("Alerts still show on screen while sounds are off").weak());
+        ui.add_space(5.0);
+
+        ui.horizontal(|ui| {
+            ui.label("Master volume:");
+            changed |= ui
+                .add(egui::Slider::new(&mut self.master_volume, 0.0..=1.0).show_value(false))
+                .changed();
+        });
+        ui.add_space(5.0);
+
+        let mut files_changed = false;
+        egui::Grid::new("event_sounds")
+            .spacing([10.0, 6.0])
+            .show(ui, |ui| {
+                for event in SoundEvent::ALL {
+                    let sound = self.events.entry(event).or_default();
+                    ui.label(event.label());
+                    let response = ui.add(
+                        egui::TextEdit::singleline(&mut sound.file)
+                            .hint_text("Built-in tone, or a WAV/OGG/FLAC/MP3 path")
+                            .desired_width(260.0),
+                    );
+                    files_changed |= response.lost_focus();
+                    changed |= response.lost_focus();
+                    egui::ComboBox::from_id_salt(("event_tone", event))
+                        .selected_text(if sound.tone.is_empty() {
+                            "Default tone"
+                        } else {
+                            sound.tone.as_str()
+                        })
+                        .show_ui(ui, |ui| {
+                            changed |= ui
+                                .selectable_value(&mut sound.tone, String::new(), "Default tone")
+                                .changed();
+                            for sequence in &self.tones {
+                                changed |= ui
+                                    .selectable_value(
+                                        &mut sound.tone,
+                                        sequence.name.clone(),
+                                        &sequence.name,
+                                    )
+                                    .changed();
+                            }
+                        });
+                    changed |= ui
+                        .add(egui::Slider::new(&mut sound.volume, 0.0..=1.0).show_value(false))
+                        .changed();
+                    if ui.small_button("▶").on_hover_text("Preview").clicked() {
+                        sound::play(event);
+                    }
+                    ui.end_row();
+
+                    if let Some(problem) = self.problems.get(&event) {
+                        ui.label("");
+                        ui.label(
+                            RichText::new(format!(
+                                "⚠ {}, the built-in tone plays instead",
+                                problem
+                            ))
+                            .small()
+                            .color(theme::palette().warning),
+                        );
+                        ui.end_row();
+                    }
+                }
+            });
+
+        ui.add_space(5.0);
+        ui.horizontal(|ui| {
+            if ui
+                .button("Reload tones")
+                .on_hover_text("Read tone definitions from ~/.rust_pomodoro_backup/tones.json")
+                .clicked()
+            {
+                self.load_tones();
+                changed = true;
+            }
+            ui.label(RichText::new(format!("{} tones", self.tones.len())).weak());
+        });
+        for error in &self.tone_errors {
+            ui.colored_label(theme::palette().danger, error);
+        }
+
+        ui.add_space(10.0);
+        ui.label(RichText::new("Focus sounds").strong());
+        ui.label(RichText::new("Plays during work phases, fades out for breaks and pauses").weak());
+        ui.horizontal(|ui| {
+            egui::ComboBox::from_id_salt("ambient_sound")
+                .selected_text(self.ambient.label())
+                .show_ui(ui, |ui| {
+                    for ambient in Ambient::ALL {
+                        if ui
+                            .selectable_value(&mut self.ambient, ambient, ambient.label())
+                            .changed()
+                        {
+                            changed = true;
+                            files_changed = true;
+                        }
+                    }
+                });
+            if self.ambient == Ambient::File {
+                let response = ui.add(
+                    egui::TextEdit::singleline(&mut self.ambient_file)
+                        .hint_text("Looped WAV/OGG/FLAC/MP3 path")
+                        .desired_width(220.0),
+                );
+                files_changed |= response.lost_focus();
+                changed |= response.lost_focus();
+            }
+            changed |= ui
+                .add(egui::Slider::new(&mut self.ambient_volume, 0.0..=1.0).show_value(false))
+                .changed();
+        });
+        if let Some(problem) = &self.ambient_problem {
+            ui.label(
+                RichText::new(format!("⚠ {}", problem))
+                    .small()
+                    .color(theme::palette().warning),
+            );
+        }
+
+        ui.add_space(10.0);
+        ui.label(RichText::new("Focus music").strong());
+        ui.label(
+            RichText::new("Your own tracks during work phases, paused for breaks and pauses")
+                .weak(),
+        );
+        ui.horizontal(|ui| {
+            changed |= ui.checkbox(&mut self.music, "Play").changed();
+            let response = ui.add(
+                egui::TextEdit::singleline(&mut self.music_source)
+                    .hint_text("Folder of audio files, or an .m3u playlist")
+                    .desired_width(220.0),
+            );
+            files_changed |= response.lost_focus();
+            changed |= response.lost_focus();
+            changed |= ui
+                .add(egui::Slider::new(&mut self.music_volume, 0.0..=1.0).show_value(false))
+                .changed();
+        });
+        match &self.music_tracks {
+            None => {}
+            Some(Ok(count)) => {
+                ui.label(RichText::new(format!("{} tracks", count)).weak());
+            }
+            Some(Err(problem)) => {
+                ui.label(
+                    RichText::new(format!("⚠ {}", problem))
+                        .small()
+                        .color(theme::palette().warning),
+                );
+            }
+        }
+
+        if files_changed {
+            self.check_files();
+        }
+        if changed {
+            self.apply();
+            self.save();
+        }
+    }
+
+    pub fn save(&self) {
+        if let Some(home_dir) = dirs::home_dir() {
+            let backup_dir = home_dir.join(".rust_pomodoro_backup");
+            fs::create_dir_all(&backup_dir).unwrap_or(());
+            let sounds_json = serde_json::to_string(&self).unwrap_or_default();
+            fs::write(backup_dir.join("sounds.json"), sounds_json).unwrap_or(());
+        }
+    }
+
+    pub fn load() -> Self {
+        let mut settings = Self::new();
+        if let Some(home_dir) = dirs::home_dir() {
+            let file_path = home_dir.join(".rust_pomodoro_backup/sounds.json");
+            if Path::new(&file_path).exists() {
+                if let Ok(contents) = fs::read_to_string(file_path) {
+                    if let Ok(loaded) = serde_json::from_str(&contents) {
+                        settings = loaded;
+                    }
+                }
+            }
+        }
+        settings.check_files();
+        settings.load_tones();
+        settings.devices = sound::output_devices();
+        settings
+    }
+}
+
+fn time_input(ui: &mut egui::Ui, (hour, minute): &mut (u32, u32)) -> bool {
+    let hour_changed = ui
+        .add(egui::DragValue::new(hour).range(0..=23).speed(0.1))
+        .changed();
+    ui.label(":");
+    let minute_changed = ui
+        .add(egui::DragValue::new(minute).range(0..=59).speed(0.2))
+        .changed();
+    hour_changed || minute_changed
+}
+
+/// Whether a sound file exists and can be decoded.
+fn check_file(path: &str) -> Result<(), String> {
+    let file = fs::File::open(path).map_err(|_| "File not found".to_string())?;
+    rodio::Decoder::new(BufReader::new(file))
+        .map(|_| ())
+        .map_err(|_| "Not a sound file that can be played".to_string())
+}
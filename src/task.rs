@@ -1,135 +1,1611 @@
-use std::{fs, path::Path, time::Duration};
+use std::{
+    collections::{HashMap, HashSet},
+    fs,
+    path::Path,
+    time::Duration,
+};
 
+use egui::{Color32, Id, RichText, Stroke};
+
+use chrono::{Local, NaiveDate, NaiveDateTime, NaiveTime, TimeDelta};
+
+use crate::archive::{Archive, ArchivedTask};
+use crate::board::{Board, BoardColumn};
+use crate::commitment::Commitments;
+use crate::filter::TaskViews;
+use crate::history::{History, Snapshot};
+use crate::planner::PlanItem;
 use crate::pomodoro::{Pomodoro, PomodoroState};
+use crate::quick_add::{self, QuickAdd};
+use crate::recurring::{Recurrence, TemplateTask, Templates};
+use crate::reminder::{Notice, Reminders};
+use crate::rollover::Rollover;
+use crate::theme;
 
 #[derive(Clone, serde::Serialize, serde::Deserialize)]
 pub struct Task {
+    #[serde(default)]
+    pub id: u64,
     pub name: String,
     pub duration: Duration,
+    #[serde(default = "default_estimate")]
+    pub estimate: u32, // estimated pomodoros
+    #[serde(default)]
+    pub project: String,
     pub completed: bool,
     pub running: bool, // tracking running task
+    #[serde(default)]
+    pub time_spent: Duration, // work time accumulated while running
+    #[serde(default)]
+    pub checklist: Vec<ChecklistItem>,
+    #[serde(default)]
+    pub auto_complete: bool, // complete the task once every checklist item is done
+    #[serde(default)]
+    pub recurrence: Option<Recurrence>,
+    #[serde(default)]
+    pub scheduled: Option<NaiveDate>, // day this instance is for, hidden until then
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default)]
+    pub priority: Priority,
+    #[serde(default)]
+    pub due: Option<NaiveDate>,
+    #[serde(default)]
+    pub column: Option<String>, // user-defined board column, None means "Todo"
+    #[serde(default)]
+    pub depends_on: Vec<u64>, // ids of tasks that must be completed first
+    #[serde(default)]
+    pub carried_days: u32, // days this task has been carried over unfinished
+    #[serde(default)]
+    pub time: Option<NaiveTime>, // time of day the task starts, or is due by if it has a due date
+    #[serde(default)]
+    pub reminded: Option<NaiveDateTime>, // the moment the last reminder was raised for
+    #[serde(default)]
+    pub sheet: Sheet,
+}
+
+/// The two lists of the Pomodoro Technique: everything you might do, and what you
+/// committed to doing today.
+#[derive(Clone, Copy, PartialEq, Default, serde::Serialize, serde::Deserialize)]
+pub enum Sheet {
+    Inventory,
+    #[default]
+    Today,
+}
+
+impl Sheet {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Sheet::Inventory => "Activity Inventory",
+            Sheet::Today => "To Do Today",
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, PartialOrd, Default, serde::Serialize, serde::Deserialize)]
+pub enum Priority {
+    #[default]
+    None,
+    Low,
+    Medium,
+    High,
+}
+
+impl Priority {
+    pub const ALL: [Priority; 4] = [
+        Priority::None,
+        Priority::Low,
+        Priority::Medium,
+        Priority::High,
+    ];
+
+    pub fn parse(text: &str) -> Option<Self> {
+        match text.to_lowercase().as_str() {
+            "high" | "h" => Some(Priority::High),
+            "medium" | "med" | "m" => Some(Priority::Medium),
+            "low" | "l" => Some(Priority::Low),
+            _ => None,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Priority::None => "None",
+            Priority::Low => "Low",
+            Priority::Medium => "Medium",
+            Priority::High => "High",
+        }
+    }
+
+    fn marker(&self) -> Option<(&'static str, Color32)> {
+        let palette = theme::palette();
+        match self {
+            Priority::None => None,
+            Priority::Low => Some(("!", palette.info)),
+            Priority::Medium => Some(("!!", palette.warning)),
+            Priority::High => Some(("!!!", palette.danger)),
+        }
+    }
+}
+
+#[derive(Clone, serde::Serialize, serde::Deserialize)]
+pub struct ChecklistItem {
+    pub text: String,
+    pub done: bool,
+}
+
+impl Task {
+    pub fn new(id: u64, name: String, duration: Duration) -> Self {
+        Self {
+            id,
+            name,
+            duration,
+            estimate: 1,
+            project: String::new(),
+            completed: false,
+            running: false,
+            time_spent: Duration::from_secs(0),
+            checklist: Vec::new(),
+            auto_complete: false,
+            recurrence: None,
+            scheduled: None,
+            tags: Vec::new(),
+            priority: Priority::None,
+            due: None,
+            column: None,
+            depends_on: Vec::new(),
+            carried_days: 0,
+            time: None,
+            reminded: None,
+            sheet: Sheet::Today,
+        }
+    }
+
+    fn is_upcoming(&self, today: NaiveDate) -> bool {
+        self.scheduled.is_some_and(|date| date > today)
+    }
+
+    /// When the task starts or is due. A dated task without a time is reminded of when its
+    /// day starts; an undated one with a time is reminded of today, and again on later days
+    /// only if it recurs.
+    pub fn reminder_at(&self, today: NaiveDate, day_start: NaiveTime) -> Option<NaiveDateTime> {
+        match (self.due.or(self.scheduled), self.time) {
+            (Some(date), time) => Some(date.and_time(time.unwrap_or(day_start))),
+            (None, Some(time)) if self.recurrence.is_some() || self.reminded.is_none() => {
+                Some(today.and_time(time))
+            }
+            (None, Some(_)) => self.reminded, // already reminded once
+            (None, None) => None,
+        }
+    }
+
+    /// Checklist progress as (done, total).
+    pub fn checklist_progress(&self) -> (usize, usize) {
+        let done = self.checklist.iter().filter(|item| item.done).count();
+        (done, self.checklist.len())
+    }
+}
+
+/// Everything an undo of a task list action needs to put back.
+pub struct TasksSnapshot {
+    tasks: Vec<Task>,
+    finished: Vec<ArchivedTask>,
+    next_id: u64,
+    committed: Vec<u64>,
+}
+
+const DEFAULT_TASK_DURATION: Duration = Duration::from_secs(25 * 60);
+
+fn default_estimate() -> u32 {
+    1
 }
 
 pub struct TaskList {
     tasks: Vec<Task>,
-    finished_tasks: Vec<Task>,
-    running_task_index: Option<usize>, // tracking the currently running task
+    finished_tasks: Archive,
+    running_task_id: Option<u64>, // tracking the currently running task
+    next_id: u64,
+    selected: HashSet<u64>,
+    editing: Option<u64>,
+    expanded: HashSet<u64>,              // tasks whose checklist is shown
+    new_item_text: HashMap<u64, String>, // per-task checklist input
+    quick_add_input: String,
+    edit_due: String,  // due date text while editing a task
+    edit_tags: String, // space separated tags while editing a task
+    edit_time: String, // time of day text while editing a task
+    bulk_project: String,
+    templates: Templates,
+    views: TaskViews,
+    board: Board,
+    rollover: Rollover,
+    review: Vec<u64>, // carried-over tasks waiting for the morning review
+    reminders: Reminders,
+    commitments: Commitments,
+    sheet: Sheet,            // the sheet being shown
+    unrecorded_change: bool, // changed outside the undo history, so older snapshots are stale
+    stop_requested: bool,    // the running task was stopped or removed, so its pomodoro ends too
 }
 
 impl TaskList {
     pub fn new() -> Self {
         Self {
             tasks: Vec::new(),
-            finished_tasks: Vec::new(),
-            running_task_index: None,
+            finished_tasks: Archive::new(),
+            running_task_id: None,
+            next_id: 1,
+            selected: HashSet::new(),
+            editing: None,
+            expanded: HashSet::new(),
+            new_item_text: HashMap::new(),
+            quick_add_input: String::new(),
+            edit_due: String::new(),
+            edit_tags: String::new(),
+            edit_time: String::new(),
+            bulk_project: String::new(),
+            templates: Templates::new(),
+            views: TaskViews::new(),
+            board: Board::new(),
+            rollover: Rollover::new(),
+            review: Vec::new(),
+            reminders: Reminders::new(),
+            commitments: Commitments::new(),
+            sheet: Sheet::Today,
+            unrecorded_change: false,
+            stop_requested: false,
+        }
+    }
+
+    pub fn snapshot(&self) -> TasksSnapshot {
+        TasksSnapshot {
+            tasks: self.tasks.clone(),
+            finished: self.finished_tasks.entries().to_vec(),
+            next_id: self.next_id,
+            committed: self.commitments.ids().to_vec(),
+        }
+    }
+
+    pub fn restore(&mut self, snapshot: TasksSnapshot) {
+        // Time tracking and reminders carry on through an undo; only what the user changed
+        // goes back
+        let live: HashMap<u64, (Duration, bool, Option<NaiveDateTime>)> = self
+            .tasks
+            .iter()
+            .map(|task| (task.id, (task.time_spent, task.running, task.reminded)))
+            .collect();
+        self.tasks = snapshot.tasks;
+        for task in self.tasks.iter_mut() {
+            let (time_spent, running, reminded) =
+                live.get(&task.id)
+                    .copied()
+                    .unwrap_or((task.time_spent, false, task.reminded));
+            task.time_spent = time_spent;
+            task.running = running;
+            task.reminded = reminded;
+        }
+        self.finished_tasks.set_entries(snapshot.finished);
+        self.next_id = self.next_id.max(snapshot.next_id);
+        self.commitments.set_ids(snapshot.committed);
+        self.running_task_id = self
+            .tasks
+            .iter()
+            .find(|task| task.running)
+            .map(|task| task.id);
+        self.selected.clear();
+        self.editing = None;
+        self.save_to_file();
+    }
+
+    /// The current working day, which only turns over at the configured day-start hour.
+    fn today(&self) -> NaiveDate {
+        self.rollover.today()
+    }
+
+    fn record(&self, history: &mut History, label: &str) {
+        history.record(label, Snapshot::Tasks(self.snapshot()));
+    }
+
+    /// Whether the list changed since the last call without going through the undo
+    /// history, e.g. a task archived by the timer or a checklist item ticked off.
+    pub fn take_unrecorded_change(&mut self) -> bool {
+        std::mem::take(&mut self.unrecorded_change)
+    }
+
+    /// Returns true once after the running task was stopped, moved to the inventory or
+    /// deleted; the caller stops the pomodoro that was timing it.
+    pub fn take_stop_request(&mut self) -> bool {
+        std::mem::take(&mut self.stop_requested)
+    }
+
+    fn add_quick_task(&mut self, parsed: QuickAdd) {
+        let duration = parsed.duration.unwrap_or(DEFAULT_TASK_DURATION);
+        let mut task = Task::new(self.next_id, parsed.name, duration);
+        task.project = parsed.project.unwrap_or_default();
+        task.tags = parsed.tags;
+        task.priority = parsed.priority.unwrap_or_default();
+        task.estimate = parsed.estimate.unwrap_or(1);
+        task.due = parsed.due;
+        task.time = parsed.time;
+        task.sheet = self.sheet;
+        if task.sheet == Sheet::Today {
+            self.commitments.commit(task.id, self.today());
+        }
+        self.tasks.push(task);
+        self.next_id += 1;
+    }
+
+    /// Moves a task between the inventory and today's sheet, updating today's commitments.
+    fn move_to_sheet(&mut self, id: u64, sheet: Sheet) {
+        if let Some(task) = self.tasks.iter_mut().find(|task| task.id == id) {
+            task.sheet = sheet;
+        }
+        match sheet {
+            Sheet::Today => self.commitments.commit(id, self.today()),
+            Sheet::Inventory => {
+                self.commitments.withdraw(id);
+                self.stop_task(id);
+            }
+        }
+    }
+
+    /// Today's commitments that still stand, and how many of them are done.
+    fn commitment_progress(&self) -> (usize, usize) {
+        let mut committed = 0;
+        let mut finished = 0;
+        for id in self.commitments.ids() {
+            if self.finished_tasks.contains(*id) {
+                committed += 1;
+                finished += 1;
+            } else if let Some(task) = self.tasks.iter().find(|task| task.id == *id) {
+                if task.sheet == Sheet::Today {
+                    committed += 1;
+                    finished += task.completed as usize;
+                }
+            }
+        }
+        (committed, finished)
+    }
+
+    fn begin_edit(&mut self, id: u64) {
+        if let Some(task) = self.tasks.iter().find(|task| task.id == id) {
+            self.edit_due = task
+                .due
+                .map(|date| date.format("%Y-%m-%d").to_string())
+                .unwrap_or_default();
+            self.edit_tags = task.tags.join(" ");
+            self.edit_time = task
+                .time
+                .map(|time| time.format("%H:%M").to_string())
+                .unwrap_or_default();
+            self.editing = Some(id);
+        }
+    }
+
+    /// Finishes editing unless a field cannot be read, in which case editing goes on so the
+    /// error stays on screen. Returns whether editing finished.
+    fn finish_edit(&mut self, id: u64) -> bool {
+        let today = self.today();
+        if !edit_errors(&self.edit_due, &self.edit_time, today).is_empty() {
+            return false;
+        }
+        if let Some(task) = self.tasks.iter_mut().find(|task| task.id == id) {
+            let due = self.edit_due.trim();
+            if due.is_empty() {
+                task.due = None;
+            } else if let Some(date) = quick_add::parse_date(due, today) {
+                task.due = Some(date);
+            }
+            let previous_time = task.time;
+            let time = self.edit_time.trim();
+            if time.is_empty() {
+                task.time = None;
+            } else if let Some(time) = quick_add::parse_time(time) {
+                task.time = Some(time);
+            }
+            if task.time != previous_time {
+                task.reminded = None; // remind again at the new time
+            }
+            task.tags = self
+                .edit_tags
+                .split([' ', ','])
+                .map(|tag| tag.trim_start_matches('#').to_lowercase())
+                .filter(|tag| !tag.is_empty())
+                .collect();
+        }
+        self.editing = None;
+        true
+    }
+
+    /// Adds tasks from a day template, skipping ones already waiting in the list.
+    fn add_from_templates(&mut self, template_tasks: Vec<TemplateTask>) {
+        for template_task in template_tasks {
+            if self
+                .tasks
+                .iter()
+                .any(|task| !task.completed && task.name == template_task.name)
+            {
+                continue;
+            }
+            let mut task = Task::new(self.next_id, template_task.name, template_task.duration);
+            task.estimate = template_task.estimate;
+            task.project = template_task.project;
+            self.commitments.commit(task.id, self.today());
+            self.tasks.push(task);
+            self.next_id += 1;
+        }
+    }
+
+    /// Queues the next occurrence of a completed recurring task. The completed instance
+    /// gives up its rule so it only ever spawns once.
+    fn spawn_next_occurrence(&mut self, index: usize) {
+        let Some(recurrence) = self.tasks[index].recurrence.take() else {
+            return;
+        };
+        let today = self.today();
+        let from = self.tasks[index].scheduled.unwrap_or(today).max(today);
+
+        let mut next = self.tasks[index].clone();
+        next.id = self.next_id;
+        next.completed = false;
+        next.running = false;
+        next.time_spent = Duration::from_secs(0);
+        next.checklist.iter_mut().for_each(|item| item.done = false);
+        next.scheduled = Some(recurrence.next_after(from));
+        next.recurrence = Some(recurrence);
+        self.tasks.push(next);
+        self.next_id += 1;
+    }
+
+    fn spawn_recurrences(&mut self) {
+        for index in 0..self.tasks.len() {
+            if self.tasks[index].completed && self.tasks[index].recurrence.is_some() {
+                self.spawn_next_occurrence(index);
+            }
+        }
+    }
+
+    fn remove_task(&mut self, id: u64) {
+        self.tasks.retain(|task| task.id != id);
+        self.selected.remove(&id);
+        if self.running_task_id == Some(id) {
+            self.running_task_id = None;
+            self.stop_requested = true;
+        }
+        if self.editing == Some(id) {
+            self.editing = None;
+        }
+    }
+
+    fn archive_task(&mut self, id: u64) {
+        if let Some(index) = self.tasks.iter().position(|task| task.id == id) {
+            self.spawn_next_occurrence(index);
+            let task = self.tasks.remove(index);
+            self.finished_tasks.push(task);
+        }
+        self.selected.remove(&id);
+        if self.running_task_id == Some(id) {
+            self.running_task_id = None;
+        }
+    }
+
+    /// Moves every checked-off task into the finished tasks archive.
+    pub fn archive_completed(&mut self) {
+        let ids: Vec<u64> = self
+            .tasks
+            .iter()
+            .filter(|task| task.completed)
+            .map(|task| task.id)
+            .collect();
+        for id in ids {
+            self.archive_task(id);
+        }
+    }
+
+    /// Starts a new day once the day-start hour has passed: completed tasks are archived,
+    /// unfinished ones are carried forward and queued for the morning review.
+    pub fn roll_over(&mut self) {
+        if let Some(days) = self.rollover.check() {
+            let (committed, finished) = self.commitment_progress();
+            self.commitments
+                .close_day(committed as u32, finished as u32);
+            self.archive_completed();
+
+            // Whatever is left on today's sheet carries over and is committed to again
+            let today = self.today();
+            for task in self.tasks.iter_mut() {
+                if task.sheet != Sheet::Today || task.is_upcoming(today) {
+                    continue;
+                }
+                if task.scheduled.map_or(true, |date| date < today) {
+                    task.carried_days += days;
+                    if !self.review.contains(&task.id) {
+                        self.review.push(task.id);
+                    }
+                }
+                self.commitments.commit(task.id, today);
+            }
+            self.save_to_file();
+            self.unrecorded_change = true;
+        }
+
+        // First launch of the day fills the list from the matching day templates
+        let template_tasks = self.templates.due_for(self.today());
+        if !template_tasks.is_empty() {
+            self.add_from_templates(template_tasks);
+            self.unrecorded_change = true;
+        }
+    }
+
+    /// Raises a reminder for every open task whose time is within the lead time.
+    pub fn check_reminders(&mut self) {
+        let now = Local::now().naive_local();
+        let today = self.today();
+        let day_start = self.rollover.day_start();
+        let lead = TimeDelta::minutes(self.reminders.lead_minutes as i64);
+        let mut notices = Vec::new();
+        for task in self.tasks.iter_mut() {
+            let Some(at) = task.reminder_at(today, day_start) else {
+                continue;
+            };
+            // Date-only tasks come up when their day starts, with no lead time
+            let remind_from = if task.time.is_some() { at - lead } else { at };
+            if task.completed || task.reminded == Some(at) || now < remind_from {
+                continue;
+            }
+            task.reminded = Some(at);
+
+            let message = if task.time.is_some() {
+                let minutes = ((at - now).num_seconds() + 59).div_euclid(60);
+                let when = match minutes {
+                    m if m > 0 => format!("in {} min", m),
+                    0 => "now".to_string(),
+                    _ => "overdue".to_string(),
+                };
+                let kind = if task.due.is_some() { "Due" } else { "Starts" };
+                format!("{} at {} ({})", kind, at.format("%H:%M"), when)
+            } else if at.date() < today {
+                format!("Overdue since {}", at.format("%b %d"))
+            } else {
+                let kind = if task.due.is_some() {
+                    "Due"
+                } else {
+                    "Scheduled"
+                };
+                format!("{} today", kind)
+            };
+            notices.push(Notice {
+                task_id: task.id,
+                title: task.name.clone(),
+                message,
+            });
+        }
+
+        if !notices.is_empty() {
+            for notice in notices {
+                self.reminders.notify(notice);
+            }
+            self.save_to_file();
+        }
+    }
+
+    pub fn reminders_ui(&mut self, ctx: &egui::Context) {
+        self.reminders.ui(ctx);
+    }
+
+    /// The task being worked on, as (id, name).
+    pub fn running_task(&self) -> Option<(u64, String)> {
+        let id = self.running_task_id?;
+        self.tasks
+            .iter()
+            .find(|task| task.id == id)
+            .map(|task| (id, task.name.clone()))
+    }
+
+    /// Tracks time spent on the running task and archives it when its work session ends.
+    /// `work_completed` is this frame's `Pomodoro::take_work_completed`.
+    pub fn tick(&mut self, elapsed: Duration, pomodoro: &mut Pomodoro, work_completed: bool) {
+        let Some(id) = self.running_task_id else {
+            return;
+        };
+
+        if pomodoro.state == PomodoroState::Work {
+            if let Some(task) = self.tasks.iter_mut().find(|task| task.id == id) {
+                task.time_spent += elapsed;
+            }
+        }
+
+        if work_completed {
+            self.archive_task(id);
+            self.unrecorded_change = true;
+            self.save_to_file();
+            pomodoro.elapsed = Duration::from_secs(0); // Reset Pomodoro
         }
     }
 
-    pub fn ui(&mut self, ui: &mut egui::Ui, pomodoro: &mut Pomodoro) {
-        ui.heading("Tasks");
+    /// The first unfinished task `task` is waiting on. Dependencies that were archived or
+    /// deleted no longer block.
+    fn blocker(&self, task: &Task) -> Option<&Task> {
+        task.depends_on.iter().find_map(|dependency| {
+            self.tasks
+                .iter()
+                .find(|other| other.id == *dependency && !other.completed)
+        })
+    }
+
+    /// Blocked task ids mapped to the name of the task blocking them.
+    pub fn blocked_tasks(&self) -> HashMap<u64, String> {
+        self.tasks
+            .iter()
+            .filter_map(|task| {
+                self.blocker(task)
+                    .map(|blocker| (task.id, blocker.name.clone()))
+            })
+            .collect()
+    }
+
+    /// Whether `from` waits on `target`, directly or through other tasks.
+    fn depends_on(&self, from: u64, target: u64) -> bool {
+        let mut stack = vec![from];
+        let mut seen = HashSet::new();
+        while let Some(id) = stack.pop() {
+            if id == target {
+                return true;
+            }
+            if !seen.insert(id) {
+                continue;
+            }
+            if let Some(task) = self.tasks.iter().find(|task| task.id == id) {
+                stack.extend(task.depends_on.iter().copied());
+            }
+        }
+        false
+    }
+
+    /// Open tasks for today in list order, running task first, with the work sessions
+    /// each still needs according to its estimate.
+    pub fn plan_items(&self) -> Vec<PlanItem> {
+        let today = self.today();
+        let mut items: Vec<PlanItem> = self
+            .tasks
+            .iter()
+            .filter(|task| {
+                !task.completed && task.sheet == Sheet::Today && !task.is_upcoming(today)
+            })
+            .map(|task| {
+                let length = task.duration.as_secs().max(1);
+                let done = (task.time_spent.as_secs() / length) as u32;
+                let mut sessions = task.estimate.saturating_sub(done);
+                if task.running {
+                    sessions = sessions.max(1); // the session in progress
+                }
+                PlanItem {
+                    name: task.name.clone(),
+                    sessions,
+                    duration: task.duration,
+                    running: task.running,
+                }
+            })
+            .filter(|item| item.sessions > 0)
+            .collect();
+        items.sort_by_key(|item| !item.running);
+        items
+    }
+
+    /// Makes `id` the active task and starts a work session with its duration.
+    fn start_task(&mut self, id: u64, pomodoro: &mut Pomodoro) {
+        if self.running_task_id == Some(id) {
+            return;
+        }
+        match self.tasks.iter().find(|task| task.id == id) {
+            Some(task) if !task.completed && self.blocker(task).is_none() => {}
+            _ => return,
+        }
+
+        for task in self.tasks.iter_mut() {
+            task.running = task.id == id;
+            if task.running {
+                pomodoro.start_work(task.duration);
+            }
+        }
+        self.running_task_id = Some(id);
+        self.move_to_sheet(id, Sheet::Today);
+    }
+
+    fn stop_task(&mut self, id: u64) {
+        if let Some(task) = self.tasks.iter_mut().find(|task| task.id == id) {
+            task.running = false;
+        }
+        if self.running_task_id == Some(id) {
+            self.running_task_id = None;
+            self.stop_requested = true;
+        }
+    }
+
+    /// Moves the task with `id` so that it sits at `target` in the list.
+    fn move_task(&mut self, id: u64, target: usize) {
+        if let Some(from) = self.tasks.iter().position(|task| task.id == id) {
+            let task = self.tasks.remove(from);
+            let target = if from < target { target - 1 } else { target };
+            self.tasks.insert(target.min(self.tasks.len()), task);
+        }
+    }
+
+    pub fn ui(&mut self, ui: &mut egui::Ui, pomodoro: &mut Pomodoro, history: &mut History) {
+        ui.horizontal(|ui| {
+            ui.heading("Tasks");
+            ui.add_space(10.0);
+            let before = self.board.enabled;
+            ui.selectable_value(&mut self.board.enabled, false, "List");
+            ui.selectable_value(&mut self.board.enabled, true, "Board");
+            if before != self.board.enabled {
+                self.board.save();
+            }
+        });
+        ui.add_space(5.0);
+
+        let today = self.today();
+        ui.horizontal(|ui| {
+            for sheet in [Sheet::Today, Sheet::Inventory] {
+                let count = self
+                    .tasks
+                    .iter()
+                    .filter(|task| task.sheet == sheet && !task.is_upcoming(today))
+                    .count();
+                if ui
+                    .selectable_label(
+                        self.sheet == sheet,
+                        format!("{} ({})", sheet.label(), count),
+                    )
+                    .clicked()
+                {
+                    self.sheet = sheet;
+                    self.selected.clear();
+                }
+            }
+        });
+        if self.sheet == Sheet::Today {
+            let (committed, finished) = self.commitment_progress();
+            ui.label(
+                RichText::new(format!(
+                    "Committed to {} tasks today, {} done",
+                    committed, finished
+                ))
+                .weak(),
+            );
+        }
+        ui.add_space(5.0);
+
+        self.filter_bar_ui(ui, today);
+        ui.add_space(5.0);
+
+        if self.board.enabled {
+            self.board_ui(ui, today, pomodoro, history);
+        } else {
+            self.list_ui(ui, today, pomodoro, history);
+        }
+
+        self.spawn_recurrences();
+
+        if !self.selected.is_empty() {
+            ui.add_space(5.0);
+            self.bulk_actions_ui(ui, history);
+        }
+
+        ui.add_space(10.0);
+        self.quick_add_ui(ui, today, history);
+
+        let upcoming: Vec<&Task> = self
+            .tasks
+            .iter()
+            .filter(|task| task.is_upcoming(today))
+            .collect();
+        if !upcoming.is_empty() {
+            ui.add_space(5.0);
+            ui.collapsing(format!("Upcoming ({})", upcoming.len()), |ui| {
+                for task in upcoming {
+                    ui.horizontal(|ui| {
+                        ui.label(&task.name);
+                        if let Some(date) = task.scheduled {
+                            ui.label(RichText::new(date.format("%a %d %b").to_string()).weak());
+                        }
+                    });
+                }
+            });
+        }
+
+        ui.add_space(5.0);
+        ui.collapsing("Commitment history", |ui| {
+            self.commitments.history_ui(ui);
+        });
+
+        ui.add_space(5.0);
+        ui.collapsing("Day settings", |ui| {
+            ui.horizontal(|ui| {
+                ui.label("New day starts at");
+                if ui
+                    .add(
+                        egui::DragValue::new(&mut self.rollover.day_start_hour)
+                            .range(0..=12)
+                            .suffix(":00"),
+                    )
+                    .on_hover_text("Work after midnight counts towards the previous day")
+                    .changed()
+                {
+                    self.rollover.save();
+                }
+            });
+            self.reminders.settings_ui(ui);
+            ui.add_space(5.0);
+            ui.label(RichText::new("Day templates").strong());
+            let apply = self.templates.ui(ui, &self.tasks);
+            if !apply.is_empty() {
+                self.record(history, "Apply template");
+                self.add_from_templates(apply);
+            }
+        });
+
         ui.add_space(10.0);
+        ui.horizontal(|ui| {
+            ui.heading("Finished Tasks");
+            if self.tasks.iter().any(|task| task.completed)
+                && ui.small_button("Archive completed").clicked()
+            {
+                self.record(history, "Archive completed tasks");
+                self.archive_completed();
+            }
+        });
+        if let Some(index) = self.finished_tasks.ui(ui) {
+            self.record(history, "Restore task");
+            let mut task = self.finished_tasks.take(index);
+            task.completed = false;
+            self.tasks.push(task);
+        }
+    }
 
+    /// Morning review of the tasks carried over from previous days.
+    pub fn review_ui(&mut self, ctx: &egui::Context, history: &mut History) {
+        self.review.retain(|id| {
+            self.tasks
+                .iter()
+                .any(|task| task.id == *id && !task.completed)
+        });
+        if self.review.is_empty() {
+            return;
+        }
+
+        let mut keep = None;
+        let mut reschedule = None;
+        let mut to_inventory = None;
+        let mut drop = None;
+        let mut keep_all = false;
+        egui::Window::new("Morning review")
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+            .show(ctx, |ui| {
+                ui.label(
+                    RichText::new("These tasks were not finished. Keep them for today?").weak(),
+                );
+                ui.add_space(5.0);
+                egui::Grid::new("morning_review")
+                    .spacing([10.0, 6.0])
+                    .show(ui, |ui| {
+                        for id in &self.review {
+                            let Some(task) = self.tasks.iter().find(|task| task.id == *id) else {
+                                continue;
+                            };
+                            ui.label(&task.name);
+                            ui.label(
+                                RichText::new(match task.carried_days {
+                                    1 => "carried 1 day".to_string(),
+                                    n => format!("carried {} days", n),
+                                })
+                                .color(theme::palette().muted),
+                            );
+                            if ui.small_button("Keep").clicked() {
+                                keep = Some(*id);
+                            }
+                            if ui.small_button("Tomorrow").clicked() {
+                                reschedule = Some(*id);
+                            }
+                            if ui.small_button("Inventory").clicked() {
+                                to_inventory = Some(*id);
+                            }
+                            if ui.small_button("Drop").clicked() {
+                                drop = Some(*id);
+                            }
+                            ui.end_row();
+                        }
+                    });
+                ui.add_space(5.0);
+                keep_all = ui.button("Keep all").clicked();
+            });
+
+        if keep_all {
+            self.review.clear();
+        }
+        if let Some(id) = keep {
+            self.review.retain(|other| *other != id);
+        }
+        if let Some(id) = reschedule {
+            self.record(history, "Reschedule task");
+            let tomorrow = self.today().succ_opt();
+            if let Some(task) = self.tasks.iter_mut().find(|task| task.id == id) {
+                task.scheduled = tomorrow;
+            }
+            self.stop_task(id);
+            self.review.retain(|other| *other != id);
+        }
+        if let Some(id) = to_inventory {
+            self.record(history, sheet_action(Sheet::Inventory));
+            self.move_to_sheet(id, Sheet::Inventory);
+            self.review.retain(|other| *other != id);
+        }
+        if let Some(id) = drop {
+            self.record_destructive(history, "Drop task");
+            self.remove_task(id);
+            self.review.retain(|other| *other != id);
+        }
+        if keep_all || keep.or(reschedule).or(to_inventory).or(drop).is_some() {
+            self.save_to_file();
+        }
+    }
+
+    fn list_ui(
+        &mut self,
+        ui: &mut egui::Ui,
+        today: NaiveDate,
+        pomodoro: &mut Pomodoro,
+        history: &mut History,
+    ) {
         let mut to_remove = None;
+        let mut to_move = None;
+        let mut toggle_edit = None;
+        let mut toggle_expand = None;
+        let mut toggled_complete = None;
+        let mut to_start = None;
+        let mut to_sheet = None;
+        let mut hidden = 0;
+        let blocked = self.blocked_tasks();
+        let palette = theme::palette();
+        // Tasks the one being edited may depend on without creating a cycle
+        let dependency_options: Vec<(u64, String)> = match self.editing {
+            Some(editing) => self
+                .tasks
+                .iter()
+                .filter(|other| {
+                    other.id != editing && !other.completed && !self.depends_on(other.id, editing)
+                })
+                .map(|other| (other.id, other.name.clone()))
+                .collect(),
+            None => Vec::new(),
+        };
+        let names: HashMap<u64, String> = self
+            .tasks
+            .iter()
+            .map(|task| (task.id, task.name.clone()))
+            .collect();
         for (i, task) in self.tasks.iter_mut().enumerate() {
-            ui.horizontal(|ui| {
-                ui.checkbox(&mut task.completed, "");
-                ui.label(&task.name);
+            if task.is_upcoming(today) || task.sheet != self.sheet {
+                continue;
+            }
+            if !self.views.current.matches(task, today) {
+                hidden += 1;
+                continue;
+            }
+
+            let row = ui.horizontal(|ui| {
+                ui.dnd_drag_source(Id::new(("task_drag", task.id)), task.id, |ui| {
+                    ui.label(RichText::new("☰").weak());
+                });
+
+                if self.editing == Some(task.id) {
+                    ui.text_edit_singleline(&mut task.name);
+                    let mut mins = task.duration.as_secs() / 60;
+                    if ui
+                        .add(
+                            egui::DragValue::new(&mut mins)
+                                .range(1..=120)
+                                .suffix(" min"),
+                        )
+                        .changed()
+                    {
+                        task.duration = Duration::from_secs(mins * 60);
+                    }
+                    ui.add(
+                        egui::DragValue::new(&mut task.estimate)
+                            .range(1..=20)
+                            .suffix(" 🍅"),
+                    );
+                    ui.add(
+                        egui::TextEdit::singleline(&mut task.project)
+                            .hint_text("project")
+                            .desired_width(80.0),
+                    );
+                    ui.checkbox(&mut task.auto_complete, "Auto-complete")
+                        .on_hover_text("Complete the task when its checklist is done");
+                    if ui.button("Done").clicked() {
+                        toggle_edit = Some(task.id);
+                    }
+                    return;
+                }
+
+                let is_expanded = self.expanded.contains(&task.id) || task.running;
+                if ui
+                    .small_button(if is_expanded { "▾" } else { "▸" })
+                    .on_hover_text("Checklist")
+                    .clicked()
+                    && !task.running
+                {
+                    toggle_expand = Some(task.id);
+                }
+                if ui.checkbox(&mut task.completed, "").changed() {
+                    toggled_complete = Some(task.id);
+                }
+                let is_selected = self.selected.contains(&task.id);
+                let blocker = blocked.get(&task.id);
+                let name = if blocker.is_some() {
+                    RichText::new(&task.name).color(palette.dim)
+                } else {
+                    RichText::new(&task.name)
+                };
+                if ui.selectable_label(is_selected, name).clicked() {
+                    if is_selected {
+                        self.selected.remove(&task.id);
+                    } else {
+                        self.selected.insert(task.id);
+                    }
+                }
+                if let Some((marker, color)) = task.priority.marker() {
+                    ui.label(RichText::new(marker).color(color).strong());
+                }
+                if !task.project.is_empty() {
+                    ui.label(RichText::new(format!("+{}", task.project)).weak());
+                }
+                for tag in &task.tags {
+                    ui.label(RichText::new(format!("#{}", tag)).weak());
+                }
+                if let Some(due) = task.due {
+                    let color = if due < today {
+                        palette.danger
+                    } else if due == today {
+                        palette.warning
+                    } else {
+                        palette.muted
+                    };
+                    let time = task
+                        .time
+                        .map(|time| time.format(" %H:%M").to_string())
+                        .unwrap_or_default();
+                    ui.label(
+                        RichText::new(format!("due {}{}", due.format("%a %d"), time)).color(color),
+                    );
+                } else if let Some(time) = task.time {
+                    ui.label(
+                        RichText::new(format!("at {}", time.format("%H:%M"))).color(palette.muted),
+                    );
+                }
                 ui.add_space(5.0);
                 let mins = task.duration.as_secs() / 60;
-                ui.label(format!("({} min)", mins));
+                ui.label(format!("({} min, {} 🍅)", mins, task.estimate));
+                if task.carried_days > 0 {
+                    ui.label(RichText::new(format!("↪ {}d", task.carried_days)).weak())
+                        .on_hover_text(format!("Carried over {} days", task.carried_days));
+                }
+                if let Some(recurrence) = &task.recurrence {
+                    ui.label("↻")
+                        .on_hover_text(format!("Repeats {}", recurrence.label()));
+                }
+                let (done, total) = task.checklist_progress();
+                if total > 0 {
+                    ui.label(RichText::new(format!("{}/{}", done, total)).color(
+                        if done == total {
+                            palette.success
+                        } else {
+                            palette.muted
+                        },
+                    ));
+                }
                 ui.label(if task.running { " (Running)" } else { "" }); // Indicate running task
 
-                if ui.button("Start").clicked()
-                    && !task.completed
-                    && self.running_task_index.is_none()
-                {
-                    task.running = true;
-                    self.running_task_index = Some(i);
-                    pomodoro.work_duration = task.duration;
-                    pomodoro.state = crate::pomodoro::PomodoroState::Work;
-                    pomodoro.elapsed = Duration::from_secs(0);
+                if let Some(blocker) = blocker {
+                    ui.label(
+                        RichText::new(format!("⛔ blocked by {}", blocker)).color(palette.muted),
+                    );
+                }
+
+                if task.sheet == Sheet::Inventory {
+                    if ui.button("Do today").clicked() {
+                        to_sheet = Some((task.id, Sheet::Today));
+                    }
+                } else {
+                    if ui
+                        .add_enabled(blocker.is_none(), egui::Button::new("Start"))
+                        .clicked()
+                        && !task.completed
+                        && self.running_task_id.is_none()
+                    {
+                        to_start = Some(task.id);
+                    }
+                    if ui
+                        .small_button("↩")
+                        .on_hover_text("Back to the Activity Inventory")
+                        .clicked()
+                    {
+                        to_sheet = Some((task.id, Sheet::Inventory));
+                    }
+                }
+                if ui.button("Edit").clicked() {
+                    toggle_edit = Some(task.id);
                 }
                 if ui.button("Delete").clicked() {
-                    to_remove = Some(i);
+                    to_remove = Some(task.id);
                 }
             });
+
+            // Drop target: insert above or below this row depending on pointer position
+            let rect = row.response.rect;
+            if let (Some(pointer), Some(_)) = (
+                ui.input(|input| input.pointer.interact_pos()),
+                row.response.dnd_hover_payload::<u64>(),
+            ) {
+                let y = if pointer.y < rect.center().y {
+                    rect.top()
+                } else {
+                    rect.bottom()
+                };
+                ui.painter().hline(
+                    rect.x_range(),
+                    y,
+                    Stroke::new(2.0, ui.visuals().selection.bg_fill),
+                );
+            }
+            if let (Some(pointer), Some(dragged)) = (
+                ui.input(|input| input.pointer.interact_pos()),
+                row.response.dnd_release_payload::<u64>(),
+            ) {
+                let target = if pointer.y < rect.center().y {
+                    i
+                } else {
+                    i + 1
+                };
+                to_move = Some((*dragged, target));
+            }
+
+            if self.editing == Some(task.id) {
+                ui.indent(("task_details", task.id), |ui| {
+                    ui.horizontal(|ui| {
+                        ui.label("Priority:");
+                        egui::ComboBox::from_id_salt(("priority", task.id))
+                            .selected_text(task.priority.label())
+                            .width(70.0)
+                            .show_ui(ui, |ui| {
+                                for priority in Priority::ALL {
+                                    ui.selectable_value(
+                                        &mut task.priority,
+                                        priority,
+                                        priority.label(),
+                                    );
+                                }
+                            });
+                        ui.label("Due:");
+                        ui.add(
+                            egui::TextEdit::singleline(&mut self.edit_due)
+                                .hint_text("fri / 2025-06-30")
+                                .desired_width(90.0),
+                        );
+                        ui.label("Time:");
+                        ui.add(
+                            egui::TextEdit::singleline(&mut self.edit_time)
+                                .hint_text("14:30")
+                                .desired_width(50.0),
+                        );
+                        ui.label("Tags:");
+                        ui.add(
+                            egui::TextEdit::singleline(&mut self.edit_tags).desired_width(100.0),
+                        );
+                    });
+                    ui.horizontal(|ui| {
+                        ui.label("Repeats:");
+                        Recurrence::edit_ui(ui, task.id, &mut task.recurrence);
+                    });
+                    ui.horizontal_wrapped(|ui| {
+                        ui.label("Depends on:");
+                        let mut to_drop = None;
+                        for dependency in &task.depends_on {
+                            let name = names.get(dependency).map_or("(finished)", |n| n.as_str());
+                            if ui
+                                .small_button(format!("{} ✖", name))
+                                .on_hover_text("Remove dependency")
+                                .clicked()
+                            {
+                                to_drop = Some(*dependency);
+                            }
+                        }
+                        if let Some(dependency) = to_drop {
+                            task.depends_on.retain(|id| *id != dependency);
+                        }
+                        egui::ComboBox::from_id_salt(("depends_on", task.id))
+                            .selected_text("Add…")
+                            .show_ui(ui, |ui| {
+                                for (id, name) in &dependency_options {
+                                    if !task.depends_on.contains(id)
+                                        && ui.selectable_label(false, name).clicked()
+                                    {
+                                        task.depends_on.push(*id);
+                                    }
+                                }
+                            });
+                    });
+                    for error in edit_errors(&self.edit_due, &self.edit_time, today) {
+                        ui.colored_label(Color32::from_rgb(235, 87, 87), error);
+                    }
+                });
+            }
+
+            // The running task's checklist stays open so items can be ticked mid-pomodoro
+            if self.editing != Some(task.id) && (self.expanded.contains(&task.id) || task.running) {
+                ui.indent(("checklist", task.id), |ui| {
+                    self.unrecorded_change |=
+                        checklist_ui(ui, task, self.new_item_text.entry(task.id).or_default());
+                });
+            }
         }
 
-        if let Some(index) = to_remove {
-            self.tasks.remove(index);
+        if hidden > 0 {
+            ui.label(RichText::new(format!("{} tasks hidden by the filter", hidden)).weak());
         }
 
-        if let Some(index) = self.running_task_index {
-            if pomodoro.state != PomodoroState::Work && pomodoro.elapsed >= pomodoro.work_duration {
-                if let Some(task) = self.tasks.get_mut(index) {
-                    task.running = false;
-                    task.completed = true;
-                    self.finished_tasks.push(task.clone());
-                    self.tasks.remove(index);
+        if let Some(id) = toggle_expand {
+            if !self.expanded.remove(&id) {
+                self.expanded.insert(id);
+            }
+        }
+
+        if let Some(id) = to_start {
+            self.start_task(id, pomodoro);
+        }
+
+        if let Some((id, sheet)) = to_sheet {
+            self.record(history, sheet_action(sheet));
+            self.move_to_sheet(id, sheet);
+        }
+
+        if let Some(id) = toggled_complete {
+            // The checkbox already flipped the flag, so flip it back in the snapshot
+            let mut snapshot = self.snapshot();
+            if let Some(task) = snapshot.tasks.iter_mut().find(|task| task.id == id) {
+                task.completed = !task.completed;
+            }
+            history.record("Complete task", Snapshot::Tasks(snapshot));
+        }
+
+        if let Some(id) = toggle_edit {
+            let was_editing = self.editing == Some(id);
+            let finished = match self.editing {
+                Some(current) => self.finish_edit(current),
+                None => true,
+            };
+            if finished && !was_editing {
+                self.record(history, "Edit task");
+                self.begin_edit(id);
+            }
+        }
+
+        if let Some((id, target)) = to_move {
+            self.record(history, "Move task");
+            self.move_task(id, target);
+        }
+
+        if let Some(id) = to_remove {
+            self.record_destructive(history, "Delete task");
+            self.remove_task(id);
+        }
+    }
+
+    fn board_ui(
+        &mut self,
+        ui: &mut egui::Ui,
+        today: NaiveDate,
+        pomodoro: &mut Pomodoro,
+        history: &mut History,
+    ) {
+        let cards: Vec<&Task> = self
+            .tasks
+            .iter()
+            .filter(|task| {
+                !task.is_upcoming(today)
+                    && task.sheet == self.sheet
+                    && self.views.current.matches(task, today)
+            })
+            .collect();
+        let done_today = self.finished_tasks.completed_on(today, &self.rollover);
+        let blocked = self.blocked_tasks();
+
+        let Some((id, column)) = self.board.ui(ui, &cards, &blocked, &done_today) else {
+            return;
+        };
+        match column {
+            BoardColumn::Todo | BoardColumn::Custom(_) => {
+                self.record(history, "Move card");
+                self.stop_task(id);
+                if let Some(task) = self.tasks.iter_mut().find(|task| task.id == id) {
+                    task.completed = false; // dragging out of Done reopens the task
+                    task.column = match column {
+                        BoardColumn::Custom(name) => Some(name),
+                        _ => None,
+                    };
                 }
-                self.running_task_index = None;
-                pomodoro.elapsed = Duration::from_secs(0); // Reset Pomodoro
+            }
+            BoardColumn::InProgress => {
+                self.record(history, "Start task");
+                if let Some(task) = self.tasks.iter_mut().find(|task| task.id == id) {
+                    task.completed = false;
+                }
+                self.start_task(id, pomodoro);
+            }
+            BoardColumn::Done => {
+                self.record(history, "Complete task");
+                self.archive_task(id);
             }
         }
+    }
 
-        ui.add_space(10.0);
-        ui.horizontal(|ui| {
-            static mut NEW_TASK_NAME: String = String::new();
-            static mut NEW_TASK_DURATION: u64 = 25;
+    fn filter_bar_ui(&mut self, ui: &mut egui::Ui, today: NaiveDate) {
+        let mut projects: Vec<String> = self
+            .tasks
+            .iter()
+            .filter(|task| !task.project.is_empty())
+            .map(|task| task.project.clone())
+            .collect();
+        projects.sort();
+        projects.dedup();
 
-            ui.text_edit_singleline(unsafe { &mut NEW_TASK_NAME });
-            ui.add(
-                egui::DragValue::new(unsafe { &mut NEW_TASK_DURATION })
-                    .range(1..=120)
-                    .suffix(" min"),
+        let mut tags: Vec<String> = self
+            .tasks
+            .iter()
+            .flat_map(|task| task.tags.iter().cloned())
+            .collect();
+        tags.sort();
+        tags.dedup();
+
+        self.views.ui(ui, &projects, &tags, today);
+    }
+
+    fn record_destructive(&self, history: &mut History, label: &str) {
+        history.record_destructive(label, Snapshot::Tasks(self.snapshot()));
+    }
+
+    fn quick_add_ui(&mut self, ui: &mut egui::Ui, today: NaiveDate, history: &mut History) {
+        let (parsed, errors) = quick_add::parse(&self.quick_add_input, today);
+        let can_add = errors.is_empty() && !parsed.name.is_empty();
+
+        let mut add = false;
+        ui.horizontal(|ui| {
+            let response = ui.add(
+                egui::TextEdit::singleline(&mut self.quick_add_input)
+                    .hint_text("Write docs +project #tag #high ~2p 45m due:fri at:14:00")
+                    .desired_width(ui.available_width() - 80.0),
             );
+            let submitted = response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+            add = ui
+                .add_enabled(can_add, egui::Button::new("Add Task"))
+                .clicked()
+                || (submitted && can_add);
+        });
 
-            if ui.button("Add Task").clicked() {
-                self.tasks.push(Task {
-                    name: unsafe { NEW_TASK_NAME.clone() },
-                    duration: Duration::from_secs(unsafe { NEW_TASK_DURATION } * 60),
-                    completed: false,
-                    running: false,
-                });
-                unsafe { NEW_TASK_NAME.clear() }
+        if self.quick_add_input.trim().is_empty() {
+            return;
+        }
+
+        // Inline preview of what the line will produce
+        ui.horizontal_wrapped(|ui| {
+            ui.label(RichText::new(&parsed.name).strong());
+            if let Some(project) = &parsed.project {
+                ui.label(RichText::new(format!("+{}", project)).weak());
+            }
+            for tag in &parsed.tags {
+                ui.label(RichText::new(format!("#{}", tag)).weak());
+            }
+            if let Some((marker, color)) = parsed.priority.and_then(|p| p.marker()) {
+                ui.label(RichText::new(marker).color(color));
+            }
+            let mins = parsed.duration.unwrap_or(DEFAULT_TASK_DURATION).as_secs() / 60;
+            ui.label(format!("{} min, {} 🍅", mins, parsed.estimate.unwrap_or(1)));
+            if let Some(due) = parsed.due {
+                ui.label(format!("due {}", due.format("%a %d %b")));
+            }
+            if let Some(time) = parsed.time {
+                ui.label(format!("at {}", time.format("%H:%M")));
             }
         });
+        for error in &errors {
+            ui.colored_label(theme::palette().danger, error);
+        }
 
-        ui.add_space(10.0);
-        ui.heading("Finished Tasks");
-        for task in &self.finished_tasks {
-            ui.horizontal(|ui| {
-                ui.label(&task.name);
-                let mins = task.duration.as_secs() / 60;
-                ui.label(format!("({} min)", mins));
-            });
+        if add {
+            self.record(history, "Add task");
+            self.add_quick_task(parsed);
+            self.quick_add_input.clear();
         }
     }
 
+    fn bulk_actions_ui(&mut self, ui: &mut egui::Ui, history: &mut History) {
+        ui.horizontal(|ui| {
+            ui.label(
+                RichText::new(format!("{} selected", self.selected.len()))
+                    .color(theme::palette().highlight),
+            );
+
+            if ui.button("Complete").clicked() {
+                self.record(history, "Complete tasks");
+                for task in self.tasks.iter_mut() {
+                    if self.selected.contains(&task.id) {
+                        task.completed = true;
+                    }
+                }
+                self.selected.clear();
+            }
+
+            let other = match self.sheet {
+                Sheet::Today => Sheet::Inventory,
+                Sheet::Inventory => Sheet::Today,
+            };
+            if ui.button(sheet_action(other)).clicked() {
+                self.record(history, sheet_action(other));
+                let ids: Vec<u64> = self.selected.iter().copied().collect();
+                for id in ids {
+                    self.move_to_sheet(id, other);
+                }
+                self.selected.clear();
+            }
+
+            if ui.button("Delete").clicked() {
+                self.record_destructive(history, "Delete tasks");
+                let ids: Vec<u64> = self.selected.iter().copied().collect();
+                for id in ids {
+                    self.remove_task(id);
+                }
+            }
+
+            ui.add(
+                egui::TextEdit::singleline(&mut self.bulk_project)
+                    .hint_text("project")
+                    .desired_width(80.0),
+            );
+            if ui.button("Move to project").clicked() {
+                self.record(history, "Move tasks to project");
+                for task in self.tasks.iter_mut() {
+                    if self.selected.contains(&task.id) {
+                        task.project = self.bulk_project.trim().to_string();
+                    }
+                }
+                self.selected.clear();
+            }
+
+            if ui.button("Clear").clicked() {
+                self.selected.clear();
+            }
+        });
+    }
+
     pub fn save_to_file(&self) {
         if let Some(home_dir) = dirs::home_dir() {
             let backup_dir = home_dir.join(".rust_pomodoro_backup");
             fs::create_dir_all(&backup_dir).unwrap_or(());
             let tasks_json = serde_json::to_string(&self.tasks).unwrap_or_default();
             fs::write(backup_dir.join("tasks.json"), tasks_json).unwrap_or(());
+            let review_json = serde_json::to_string(&self.review).unwrap_or_default();
+            fs::write(backup_dir.join("review.json"), review_json).unwrap_or(());
         }
     }
 
     pub fn load_from_file() -> Self {
+        let mut list = Self::new();
         if let Some(home_dir) = dirs::home_dir() {
             let file_path = home_dir.join(".rust_pomodoro_backup/tasks.json");
             if Path::new(&file_path).exists() {
                 if let Ok(contents) = fs::read_to_string(file_path) {
-                    if let Ok(tasks) = serde_json::from_str(&contents) {
-                        return Self {
-                            tasks,
-                            finished_tasks: Vec::new(),
-                            running_task_index: None,
-                        };
+                    if let Ok(tasks) = serde_json::from_str::<Vec<Task>>(&contents) {
+                        list.tasks = tasks;
                     }
                 }
             }
+            // Tasks still waiting for the morning review when the app was closed
+            let file_path = home_dir.join(".rust_pomodoro_backup/review.json");
+            if let Ok(contents) = fs::read_to_string(file_path) {
+                if let Ok(review) = serde_json::from_str(&contents) {
+                    list.review = review;
+                }
+            }
         }
-        Self::new()
+
+        list.finished_tasks = Archive::load();
+
+        // Tasks saved before ids existed all come back as 0; give them unique ones
+        let max_id = list.tasks.iter().map(|task| task.id).max().unwrap_or(0);
+        list.next_id = max_id.max(list.finished_tasks.max_id()) + 1;
+        for task in list.tasks.iter_mut() {
+            if task.id == 0 {
+                task.id = list.next_id;
+                list.next_id += 1;
+            }
+            task.running = false; // nothing is running after a restart
+        }
+
+        list.views = TaskViews::load();
+        list.board = Board::load();
+        list.templates = Templates::load();
+        list.rollover = Rollover::load();
+        list.reminders = Reminders::load();
+        list.commitments = Commitments::load();
+        list.roll_over();
+        list
+    }
+}
+
+fn sheet_action(sheet: Sheet) -> &'static str {
+    match sheet {
+        Sheet::Today => "Do today",
+        Sheet::Inventory => "Move to inventory",
+    }
+}
+
+/// What is wrong with the details typed while editing, worded like the quick-add errors.
+fn edit_errors(due: &str, time: &str, today: NaiveDate) -> Vec<String> {
+    let (due, time) = (due.trim(), time.trim());
+    let mut errors = Vec::new();
+    if !due.is_empty() {
+        if let Err(error) = quick_add::parse_due(due, today) {
+            errors.push(error);
+        }
+    }
+    if !time.is_empty() {
+        if let Err(error) = quick_add::parse_at(time) {
+            errors.push(error);
+        }
+    }
+    errors
+}
+
+/// Returns whether the checklist changed.
+fn checklist_ui(ui: &mut egui::Ui, task: &mut Task, new_item_text: &mut String) -> bool {
+    let mut to_remove = None;
+    let mut toggled = false;
+    for (i, item) in task.checklist.iter_mut().enumerate() {
+        ui.horizontal(|ui| {
+            toggled |= ui.checkbox(&mut item.done, &item.text).changed();
+            if ui.small_button("✖").clicked() {
+                to_remove = Some(i);
+            }
+        });
+    }
+
+    let mut changed = toggled;
+    if let Some(index) = to_remove {
+        task.checklist.remove(index);
+        changed = true;
+    }
+
+    ui.horizontal(|ui| {
+        let response = ui.add(
+            egui::TextEdit::singleline(new_item_text)
+                .hint_text("Add a step")
+                .desired_width(160.0),
+        );
+        let submitted = response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+        if (ui.small_button("+").clicked() || submitted) && !new_item_text.trim().is_empty() {
+            task.checklist.push(ChecklistItem {
+                text: new_item_text.trim().to_string(),
+                done: false,
+            });
+            new_item_text.clear();
+            changed = true;
+        }
+    });
+
+    let (done, total) = task.checklist_progress();
+    if toggled && task.auto_complete && total > 0 && done == total {
+        task.completed = true;
     }
+    changed
 }
@@ -1,31 +1,630 @@
-// File: src/theme.rs
+// Named palettes for the UI chrome and the phase colours. Besides the built-in ones, every
+// file in ~/.rust_pomodoro_backup/themes is a theme, e.g.
+//
+//   { "name": "Solarized", "base": "Light",
+//     "colors": { "panel": "#fdf6e3", "text": "#586e75", "work": "#dc322f" } }
+//
+// Colours that are left out come from `base`. The files are re-read as they change.
 use eframe::egui::{self, Color32, Stroke, Visuals};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashMap,
+    fs,
+    path::Path,
+    sync::RwLock,
+    time::{Duration, Instant, SystemTime},
+};
 
-pub fn set_theme(ctx: &egui::Context) {
-    let mut visuals = Visuals {
-        dark_mode: true,
-        override_text_color: Some(Color32::from_rgb(220, 220, 220)),
-        panel_fill: Color32::from_rgb(30, 30, 35),
-        window_fill: Color32::from_rgb(30, 30, 35),
-        faint_bg_color: Color32::from_rgb(40, 40, 45),
-        extreme_bg_color: Color32::from_rgb(20, 20, 25),
-        code_bg_color: Color32::from_rgb(40, 40, 45),
-        window_stroke: Stroke::new(1.0, Color32::from_rgb(60, 60, 65)),
-        ..Default::default()
-    };
-
-    // Customize widget visuals
-    visuals.widgets.noninteractive.bg_fill = Color32::from_rgb(40, 40, 45);
-    visuals.widgets.noninteractive.fg_stroke = Stroke::new(1.0, Color32::from_rgb(140, 140, 145));
-
-    visuals.widgets.inactive.bg_fill = Color32::from_rgb(50, 50, 55);
-    visuals.widgets.inactive.fg_stroke = Stroke::new(1.0, Color32::from_rgb(160, 160, 165));
-
-    visuals.widgets.hovered.bg_fill = Color32::from_rgb(60, 60, 65);
-    visuals.widgets.hovered.fg_stroke = Stroke::new(1.0, Color32::from_rgb(200, 200, 205));
-
-    visuals.widgets.active.bg_fill = Color32::from_rgb(70, 70, 75);
-    visuals.widgets.active.fg_stroke = Stroke::new(1.0, Color32::from_rgb(220, 220, 225));
-
-    ctx.set_visuals(visuals);
+/// Every colour the app draws with. Widgets read the active one through `palette()`.
+#[derive(Clone, Copy)]
+pub struct Palette {
+    pub text: Color32,
+    pub panel: Color32,
+    pub window: Color32,
+    pub faint: Color32,   // striped rows and inactive backgrounds
+    pub extreme: Color32, // text edit backgrounds
+    pub border: Color32,
+    pub widget: Color32,
+    pub widget_hover: Color32,
+    pub widget_active: Color32,
+    pub widget_text: Color32,
+    pub muted: Color32,  // secondary text
+    pub dim: Color32,    // done tasks and the empty part of progress rings
+    pub strong: Color32, // countdowns and clock hands
+    pub work: Color32,
+    pub short_break: Color32,
+    pub long_break: Color32,
+    pub idle: Color32, // stopped or paused
+    pub info: Color32,
+    pub warning: Color32,
+    pub danger: Color32,
+    pub success: Color32,
+    pub highlight: Color32,
+}
+
+const DARK: Palette = Palette {
+    text: Color32::from_rgb(220, 220, 220),
+    panel: Color32::from_rgb(30, 30, 35),
+    window: Color32::from_rgb(30, 30, 35),
+    faint: Color32::from_rgb(40, 40, 45),
+    extreme: Color32::from_rgb(20, 20, 25),
+    border: Color32::from_rgb(60, 60, 65),
+    widget: Color32::from_rgb(50, 50, 55),
+    widget_hover: Color32::from_rgb(60, 60, 65),
+    widget_active: Color32::from_rgb(70, 70, 75),
+    widget_text: Color32::from_rgb(160, 160, 165),
+    muted: Color32::GRAY,
+    dim: Color32::DARK_GRAY,
+    strong: Color32::WHITE,
+    work: Color32::from_rgb(235, 87, 87),
+    short_break: Color32::from_rgb(106, 176, 76),
+    long_break: Color32::from_rgb(79, 134, 198),
+    idle: Color32::GRAY,
+    info: Color32::from_rgb(79, 134, 198),
+    warning: Color32::from_rgb(252, 186, 3),
+    danger: Color32::from_rgb(235, 87, 87),
+    success: Color32::from_rgb(106, 176, 76),
+    highlight: Color32::LIGHT_BLUE,
+};
+
+const MIDNIGHT: Palette = Palette {
+    text: Color32::from_rgb(215, 222, 235),
+    panel: Color32::from_rgb(18, 22, 33),
+    window: Color32::from_rgb(22, 27, 40),
+    faint: Color32::from_rgb(30, 36, 52),
+    extreme: Color32::from_rgb(12, 15, 24),
+    border: Color32::from_rgb(48, 56, 78),
+    widget: Color32::from_rgb(38, 45, 64),
+    widget_hover: Color32::from_rgb(50, 59, 84),
+    widget_active: Color32::from_rgb(62, 73, 104),
+    widget_text: Color32::from_rgb(160, 170, 190),
+    muted: Color32::from_rgb(130, 140, 160),
+    dim: Color32::from_rgb(70, 80, 100),
+    strong: Color32::WHITE,
+    work: Color32::from_rgb(239, 98, 108),
+    short_break: Color32::from_rgb(98, 190, 140),
+    long_break: Color32::from_rgb(110, 150, 230),
+    idle: Color32::from_rgb(130, 140, 160),
+    info: Color32::from_rgb(110, 150, 230),
+    warning: Color32::from_rgb(240, 190, 80),
+    danger: Color32::from_rgb(239, 98, 108),
+    success: Color32::from_rgb(98, 190, 140),
+    highlight: Color32::from_rgb(130, 170, 255),
+};
+
+const ESPRESSO: Palette = Palette {
+    text: Color32::from_rgb(230, 220, 205),
+    panel: Color32::from_rgb(36, 30, 28),
+    window: Color32::from_rgb(40, 34, 31),
+    faint: Color32::from_rgb(50, 42, 38),
+    extreme: Color32::from_rgb(26, 22, 20),
+    border: Color32::from_rgb(72, 62, 56),
+    widget: Color32::from_rgb(60, 51, 46),
+    widget_hover: Color32::from_rgb(74, 63, 57),
+    widget_active: Color32::from_rgb(88, 75, 68),
+    widget_text: Color32::from_rgb(180, 168, 152),
+    muted: Color32::from_rgb(160, 148, 132),
+    dim: Color32::from_rgb(100, 90, 80),
+    strong: Color32::from_rgb(250, 244, 235),
+    work: Color32::from_rgb(224, 108, 80),
+    short_break: Color32::from_rgb(150, 180, 90),
+    long_break: Color32::from_rgb(110, 150, 180),
+    idle: Color32::from_rgb(160, 148, 132),
+    info: Color32::from_rgb(110, 150, 180),
+    warning: Color32::from_rgb(230, 180, 70),
+    danger: Color32::from_rgb(224, 90, 70),
+    success: Color32::from_rgb(150, 180, 90),
+    highlight: Color32::from_rgb(140, 180, 220),
+};
+
+const LIGHT: Palette = Palette {
+    text: Color32::from_rgb(30, 30, 35),
+    panel: Color32::from_rgb(245, 245, 247),
+    window: Color32::from_rgb(250, 250, 252),
+    faint: Color32::from_rgb(232, 232, 236),
+    extreme: Color32::WHITE,
+    border: Color32::from_rgb(200, 200, 205),
+    widget: Color32::from_rgb(225, 225, 230),
+    widget_hover: Color32::from_rgb(210, 210, 216),
+    widget_active: Color32::from_rgb(195, 195, 202),
+    widget_text: Color32::from_rgb(60, 60, 65),
+    muted: Color32::from_rgb(120, 120, 125),
+    dim: Color32::from_rgb(185, 185, 190),
+    strong: Color32::from_rgb(20, 20, 25),
+    work: Color32::from_rgb(214, 69, 69),
+    short_break: Color32::from_rgb(76, 150, 52),
+    long_break: Color32::from_rgb(52, 108, 176),
+    idle: Color32::from_rgb(130, 130, 135),
+    info: Color32::from_rgb(52, 108, 176),
+    warning: Color32::from_rgb(200, 130, 0),
+    danger: Color32::from_rgb(214, 69, 69),
+    success: Color32::from_rgb(76, 150, 52),
+    highlight: Color32::from_rgb(40, 120, 200),
+};
+
+const HIGH_CONTRAST: Palette = Palette {
+    text: Color32::WHITE,
+    panel: Color32::BLACK,
+    window: Color32::BLACK,
+    faint: Color32::from_rgb(25, 25, 25),
+    extreme: Color32::BLACK,
+    border: Color32::WHITE,
+    widget: Color32::from_rgb(40, 40, 40),
+    widget_hover: Color32::from_rgb(80, 80, 80),
+    widget_active: Color32::from_rgb(120, 120, 120),
+    widget_text: Color32::WHITE,
+    muted: Color32::from_rgb(200, 200, 200),
+    dim: Color32::from_rgb(140, 140, 140),
+    strong: Color32::WHITE,
+    work: Color32::from_rgb(255, 80, 80),
+    short_break: Color32::from_rgb(80, 255, 120),
+    long_break: Color32::from_rgb(90, 180, 255),
+    idle: Color32::from_rgb(200, 200, 200),
+    info: Color32::from_rgb(90, 180, 255),
+    warning: Color32::from_rgb(255, 230, 0),
+    danger: Color32::from_rgb(255, 80, 80),
+    success: Color32::from_rgb(80, 255, 120),
+    highlight: Color32::from_rgb(0, 255, 255),
+};
+
+static PALETTE: RwLock<Palette> = RwLock::new(DARK);
+
+/// The palette of the theme in use.
+pub fn palette() -> Palette {
+    PALETTE.read().map(|palette| *palette).unwrap_or(DARK)
+}
+
+impl Palette {
+    /// The colour called `name` in theme files.
+    fn get_mut(&mut self, name: &str) -> Option<&mut Color32> {
+        Some(match name {
+            "text" => &mut self.text,
+            "panel" => &mut self.panel,
+            "window" => &mut self.window,
+            "faint" => &mut self.faint,
+            "extreme" => &mut self.extreme,
+            "border" => &mut self.border,
+            "widget" => &mut self.widget,
+            "widget_hover" => &mut self.widget_hover,
+            "widget_active" => &mut self.widget_active,
+            "widget_text" => &mut self.widget_text,
+            "muted" => &mut self.muted,
+            "dim" => &mut self.dim,
+            "strong" => &mut self.strong,
+            "work" => &mut self.work,
+            "short_break" => &mut self.short_break,
+            "long_break" => &mut self.long_break,
+            "idle" => &mut self.idle,
+            "info" => &mut self.info,
+            "warning" => &mut self.warning,
+            "danger" => &mut self.danger,
+            "success" => &mut self.success,
+            "highlight" => &mut self.highlight,
+            _ => return None,
+        })
+    }
+}
+
+#[derive(Clone)]
+pub struct Theme {
+    pub name: String,
+    pub dark: bool,
+    pub palette: Palette,
+}
+
+impl Theme {
+    fn new(name: &str, dark: bool, palette: Palette) -> Self {
+        Self {
+            name: name.to_string(),
+            dark,
+            palette,
+        }
+    }
+
+    fn visuals(&self) -> Visuals {
+        let palette = &self.palette;
+        let mut visuals = if self.dark {
+            Visuals::dark()
+        } else {
+            Visuals::light()
+        };
+        visuals.override_text_color = Some(palette.text);
+        visuals.panel_fill = palette.panel;
+        visuals.window_fill = palette.window;
+        visuals.faint_bg_color = palette.faint;
+        visuals.extreme_bg_color = palette.extreme;
+        visuals.code_bg_color = palette.faint;
+        visuals.window_stroke = Stroke::new(1.0, palette.border);
+        visuals.hyperlink_color = palette.highlight;
+
+        // Customize widget visuals
+        let widgets = &mut visuals.widgets;
+        widgets.noninteractive.bg_fill = palette.faint;
+        widgets.noninteractive.bg_stroke = Stroke::new(1.0, palette.border);
+        widgets.noninteractive.fg_stroke = Stroke::new(1.0, palette.muted);
+
+        widgets.inactive.bg_fill = palette.widget;
+        widgets.inactive.weak_bg_fill = palette.widget;
+        widgets.inactive.fg_stroke = Stroke::new(1.0, palette.widget_text);
+
+        widgets.hovered.bg_fill = palette.widget_hover;
+        widgets.hovered.weak_bg_fill = palette.widget_hover;
+        widgets.hovered.fg_stroke = Stroke::new(1.0, palette.text);
+
+        widgets.active.bg_fill = palette.widget_active;
+        widgets.active.weak_bg_fill = palette.widget_active;
+        widgets.active.fg_stroke = Stroke::new(1.0, palette.strong);
+
+        visuals
+    }
+}
+
+/// The themes the app ships with. "Dark" is the original look.
+pub fn builtin() -> Vec<Theme> {
+    vec![
+        Theme::new("Dark", true, DARK),
+        Theme::new("Midnight", true, MIDNIGHT),
+        Theme::new("Espresso", true, ESPRESSO),
+        Theme::new("Light", false, LIGHT),
+        Theme::new("High contrast", true, HIGH_CONTRAST),
+    ]
+}
+
+/// A theme as written in a file: a base theme and the colours that differ from it.
+#[derive(Serialize, Deserialize)]
+struct ThemeFile {
+    name: String,
+    #[serde(default = "default_base")]
+    base: String,
+    #[serde(default)]
+    dark: Option<bool>, // defaults to the base theme's
+    #[serde(default)]
+    colors: HashMap<String, String>,
+}
+
+fn default_base() -> String {
+    "Dark".to_string()
+}
+
+impl ThemeFile {
+    fn resolve(self, themes: &[Theme]) -> Result<Theme, String> {
+        let base = themes
+            .iter()
+            .find(|theme| theme.name == self.base)
+            .ok_or_else(|| format!("Theme \"{}\": no base theme \"{}\"", self.name, self.base))?;
+        let mut palette = base.palette;
+        for (name, value) in &self.colors {
+            let color = palette
+                .get_mut(name)
+                .ok_or_else(|| format!("Theme \"{}\": unknown colour `{}`", self.name, name))?;
+            *color = parse_color(value)
+                .ok_or_else(|| format!("Theme \"{}\": `{}` is not a colour", self.name, value))?;
+        }
+        Ok(Theme::new(
+            &self.name,
+            self.dark.unwrap_or(base.dark),
+            palette,
+        ))
+    }
+}
+
+/// `#rrggbb` or `#rrggbbaa`.
+fn parse_color(text: &str) -> Option<Color32> {
+    let hex = text.trim().strip_prefix('#')?;
+    if !hex.is_ascii() || (hex.len() != 6 && hex.len() != 8) {
+        return None;
+    }
+    let channel = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).ok();
+    let alpha = if hex.len() == 8 { channel(6)? } else { 255 };
+    Some(Color32::from_rgba_unmultiplied(
+        channel(0)?,
+        channel(2)?,
+        channel(4)?,
+        alpha,
+    ))
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct Themes {
+    pub selected: String,
+    pub follow_system: bool,
+    pub light: String, // used instead of `selected` while the system is in light mode
+    #[serde(skip)]
+    themes: Vec<Theme>, // built-in themes and the user's theme files
+    #[serde(skip)]
+    errors: Vec<String>,
+    #[serde(skip)]
+    stamp: Option<(usize, SystemTime)>, // theme file count and newest change, to spot edits
+    #[serde(skip)]
+    checked: Option<Instant>,
+    #[serde(skip)]
+    applied: Option<String>,
+}
+
+impl Themes {
+    pub fn new() -> Self {
+        Self {
+            selected: "Dark".to_string(),
+            follow_system: false,
+            light: "Light".to_string(),
+            themes: builtin(),
+            errors: Vec::new(),
+            stamp: None,
+            checked: None,
+            applied: None,
+        }
+    }
+
+    fn load_themes(&mut self) {
+        self.themes = builtin();
+        self.errors.clear();
+        let Some(home_dir) = dirs::home_dir() else {
+            return;
+        };
+        let themes_dir = home_dir.join(".rust_pomodoro_backup/themes");
+        if !Path::new(&themes_dir).exists() {
+            // Seed the folder with an example to start from
+            fs::create_dir_all(&themes_dir).unwrap_or(());
+            let example = ThemeFile {
+                name: "Solarized light".to_string(),
+                base: "Light".to_string(),
+                dark: None,
+                colors: [
+                    ("panel", "#fdf6e3"),
+                    ("window", "#fdf6e3"),
+                    ("faint", "#eee8d5"),
+                    ("text", "#586e75"),
+                    ("work", "#dc322f"),
+                    ("short_break", "#859900"),
+                    ("long_break", "#268bd2"),
+                ]
+                .iter()
+                .map(|(name, value)| (name.to_string(), value.to_string()))
+                .collect(),
+            };
+            let example_json = serde_json::to_string_pretty(&example).unwrap_or_default();
+            fs::write(themes_dir.join("solarized_light.json"), example_json).unwrap_or(());
+        }
+
+        let mut paths: Vec<_> = fs::read_dir(&themes_dir)
+            .map(|entries| {
+                entries
+                    .filter_map(|entry| entry.ok().map(|e| e.path()))
+                    .collect()
+            })
+            .unwrap_or_default();
+        paths.retain(|path| {
+            path.extension()
+                .is_some_and(|extension| extension == "json")
+        });
+        paths.sort();
+        for path in paths {
+            let file_name = path.file_name().unwrap_or_default().to_string_lossy();
+            let loaded = fs::read_to_string(&path)
+                .map_err(|error| error.to_string())
+                .and_then(|contents| {
+                    serde_json::from_str::<ThemeFile>(&contents).map_err(|e| e.to_string())
+                })
+                .map_err(|error| format!("{}: {}", file_name, error))
+                .and_then(|file| file.resolve(&self.themes));
+            match loaded {
+                Ok(theme) => {
+                    self.themes.retain(|other| other.name != theme.name);
+                    self.themes.push(theme);
+                }
+                Err(error) => self.errors.push(error),
+            }
+        }
+    }
+
+    /// Theme file count and the newest modification time.
+    fn files_stamp() -> Option<(usize, SystemTime)> {
+        let themes_dir = dirs::home_dir()?.join(".rust_pomodoro_backup/themes");
+        let times: Vec<SystemTime> = fs::read_dir(themes_dir)
+            .ok()?
+            .filter_map(|entry| entry.ok()?.metadata().ok()?.modified().ok())
+            .collect();
+        Some((times.len(), times.into_iter().max()?))
+    }
+
+    /// The theme to show now, following the system's light or dark mode if asked to.
+    fn wanted(&self, ctx: &egui::Context) -> &str {
+        if self.follow_system && ctx.system_theme() == Some(egui::Theme::Light) {
+            &self.light
+        } else {
+            &self.selected
+        }
+    }
+
+    /// Reloads changed theme files about once a second and applies the wanted theme if it
+    /// is not the one showing. Cheap enough to call every frame.
+    pub fn update(&mut self, ctx: &egui::Context) {
+        if self
+            .checked
+            .map_or(true, |checked| checked.elapsed() >= Duration::from_secs(1))
+        {
+            self.checked = Some(Instant::now());
+            let stamp = Self::files_stamp();
+            if stamp != self.stamp {
+                self.stamp = stamp;
+                self.load_themes();
+                self.applied = None;
+            }
+        }
+        if self.applied.as_deref() != Some(self.wanted(ctx)) {
+            self.apply(ctx);
+        }
+    }
+
+    fn apply(&mut self, ctx: &egui::Context) {
+        let wanted = self.wanted(ctx).to_string();
+        let theme = self
+            .themes
+            .iter()
+            .find(|theme| theme.name == wanted)
+            .unwrap_or(&self.themes[0]);
+        ctx.set_theme(if theme.dark {
+            egui::Theme::Dark
+        } else {
+            egui::Theme::Light
+        });
+        ctx.set_visuals(theme.visuals());
+        if let Ok(mut palette) = PALETTE.write() {
+            *palette = theme.palette;
+        }
+        self.applied = Some(wanted);
+    }
+
+    pub fn settings_ui(&mut self, ui: &mut egui::Ui) {
+        ui.heading("Theme");
+        ui.add_space(5.0);
+        let mut changed = false;
+        ui.horizontal(|ui| {
+            ui.label(if self.follow_system {
+                "Dark mode theme:"
+            } else {
+                "Theme:"
+            });
+            changed |= theme_combo(ui, "theme", &mut self.selected, &self.themes);
+        });
+        ui.horizontal(|ui| {
+            changed |= ui
+                .checkbox(&mut self.follow_system, "Follow the system, using")
+                .changed();
+            ui.add_enabled_ui(self.follow_system, |ui| {
+                changed |= theme_combo(ui, "light_theme", &mut self.light, &self.themes);
+            });
+            ui.label("in light mode");
+        });
+        ui.label(
+            egui::RichText::new(
+                "Theme files in ~/.rust_pomodoro_backup/themes are picked up as they change",
+            )
+            .weak(),
+        );
+        let danger = palette().danger;
+        for error in &self.errors {
+            ui.colored_label(danger, error);
+        }
+        if changed {
+            self.save();
+        }
+    }
+
+    pub fn save(&self) {
+        if let Some(home_dir) = dirs::home_dir() {
+            let backup_dir = home_dir.join(".rust_pomodoro_backup");
+            fs::create_dir_all(&backup_dir).unwrap_or(());
+            let theme_json = serde_json::to_string(&self).unwrap_or_default();
+            fs::write(backup_dir.join("theme.json"), theme_json).unwrap_or(());
+        }
+    }
+
+    pub fn load() -> Self {
+        let mut themes = Self::new();
+        if let Some(home_dir) = dirs::home_dir() {
+            let file_path = home_dir.join(".rust_pomodoro_backup/theme.json");
+            if Path::new(&file_path).exists() {
+                if let Ok(contents) = fs::read_to_string(file_path) {
+                    if let Ok(loaded) = serde_json::from_str(&contents) {
+                        themes = loaded;
+                    }
+                }
+            }
+        }
+        themes.load_themes();
+        themes.stamp = Self::files_stamp();
+        themes
+    }
+}
+
+fn theme_combo(ui: &mut egui::Ui, id: &str, selected: &mut String, themes: &[Theme]) -> bool {
+    let mut changed = false;
+    egui::ComboBox::from_id_salt(id)
+        .selected_text(selected.as_str())
+        .show_ui(ui, |ui| {
+            for theme in themes {
+                changed |= ui
+                    .selectable_value(selected, theme.name.clone(), &theme.name)
+                    .changed();
+            }
+        });
+    changed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn theme_file(json: &str) -> ThemeFile {
+        serde_json::from_str(json).unwrap()
+    }
+
+    fn resolve_error(json: &str) -> String {
+        match theme_file(json).resolve(&builtin()) {
+            Ok(theme) => panic!("\"{}\" should not resolve", theme.name),
+            Err(error) => error,
+        }
+    }
+
+    #[test]
+    fn parses_hex_colours() {
+        assert_eq!(parse_color("#ff8000"), Some(Color32::from_rgb(255, 128, 0)));
+        assert_eq!(
+            parse_color(" #FF800080 "),
+            Some(Color32::from_rgba_unmultiplied(255, 128, 0, 128))
+        );
+    }
+
+    #[test]
+    fn rejects_bad_hex() {
+        for text in [
+            "", "ff8000", "#", "#ff80", "#ff8000f", "#gg8000", "#ab€c", "#ff 800",
+        ] {
+            assert_eq!(parse_color(text), None, "{}", text);
+        }
+    }
+
+    #[test]
+    fn overrides_colours_on_top_of_the_base() {
+        let theme = theme_file(
+            r##"{ "name": "Solarized", "base": "Light",
+                  "colors": { "panel": "#fdf6e3", "work": "#dc322f" } }"##,
+        )
+        .resolve(&builtin())
+        .unwrap();
+        assert_eq!(theme.name, "Solarized");
+        assert!(!theme.dark); // taken from Light
+        assert_eq!(theme.palette.panel, Color32::from_rgb(0xfd, 0xf6, 0xe3));
+        assert_eq!(theme.palette.work, Color32::from_rgb(0xdc, 0x32, 0x2f));
+        assert_eq!(theme.palette.text, LIGHT.text);
+    }
+
+    #[test]
+    fn base_defaults_to_dark() {
+        let theme = theme_file(r#"{ "name": "Plain", "dark": false }"#)
+            .resolve(&builtin())
+            .unwrap();
+        assert!(!theme.dark);
+        assert_eq!(theme.palette.panel, DARK.panel);
+    }
+
+    #[test]
+    fn missing_base_theme_is_an_error() {
+        let error = resolve_error(r#"{ "name": "Orphan", "base": "Sepia" }"#);
+        assert!(
+            error.contains("Orphan") && error.contains("Sepia"),
+            "{}",
+            error
+        );
+    }
+
+    #[test]
+    fn unknown_colour_names_and_bad_values_are_errors() {
+        let error = resolve_error(r##"{ "name": "Typo", "colors": { "backgrnd": "#000000" } }"##);
+        assert!(error.contains("backgrnd"), "{}", error);
+
+        let error = resolve_error(r#"{ "name": "Named", "colors": { "work": "red" } }"#);
+        assert!(error.contains("`red`"), "{}", error);
+    }
 }
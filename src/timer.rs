@@ -1,24 +1,85 @@
 // File: src/timer.rs
-use crate::sound::play_alarm;
+use crate::history::{History, Snapshot};
+use crate::sound::{self, SoundEvent};
+use crate::theme;
+use crate::typography;
 use eframe::egui;
-use egui::{Align2, Color32, FontId, RichText, Stroke, Ui, Vec2};
-use std::time::Duration;
+use egui::{Align2, FontId, RichText, Stroke, Ui, Vec2};
+use serde::{Deserialize, Serialize};
+use std::{fs, path::Path, time::Duration};
 
-#[derive(PartialEq)]
+#[derive(PartialEq, Clone, Copy)]
 pub enum TimerState {
     Stopped,
     Running,
     Paused,
     Completed,
+    Snoozed,
 }
 
-pub struct Timer {
+const ALARM_INTERVAL: Duration = Duration::from_secs(4);
+
+/// Timer position saved before Reset so it can be undone.
+pub struct TimerSnapshot {
     state: TimerState,
     elapsed: Duration,
     duration: Duration,
+    next_ring: Duration,
+}
+
+/// How the alarm behaves when the timer runs out, kept between sessions.
+#[derive(Serialize, Deserialize)]
+struct AlarmSettings {
+    repeat: bool,   // ring until dismissed instead of once
+    escalate: bool, // every repeat a little louder
+    snooze_minutes: u32,
+}
+
+impl AlarmSettings {
+    fn new() -> Self {
+        Self {
+            repeat: false,
+            escalate: true,
+            snooze_minutes: 5,
+        }
+    }
+
+    fn save(&self) {
+        if let Some(home_dir) = dirs::home_dir() {
+            let backup_dir = home_dir.join(".rust_pomodoro_backup");
+            fs::create_dir_all(&backup_dir).unwrap_or(());
+            let alarm_json = serde_json::to_string(&self).unwrap_or_default();
+            fs::write(backup_dir.join("alarm.json"), alarm_json).unwrap_or(());
+        }
+    }
+
+    fn load() -> Self {
+        if let Some(home_dir) = dirs::home_dir() {
+            let file_path = home_dir.join(".rust_pomodoro_backup/alarm.json");
+            if Path::new(&file_path).exists() {
+                if let Ok(contents) = fs::read_to_string(file_path) {
+                    if let Ok(alarm) = serde_json::from_str(&contents) {
+                        return alarm;
+                    }
+                }
+            }
+        }
+        Self::new()
+    }
+}
+
+pub struct Timer {
+    state: TimerState,
+    elapsed: Duration, // keeps counting past `duration` while the alarm is up
+    duration: Duration,
     hours: u32,
     minutes: u32,
     seconds: u32,
+    alarm: AlarmSettings,
+    ringing: bool,
+    rings: u32,
+    next_ring: Duration, // elapsed time at which the alarm rings again
+    finished: bool,      // set when the countdown runs out, cleared by take_finished
 }
 
 impl Timer {
@@ -30,23 +91,104 @@ impl Timer {
             hours: 0,
             minutes: 5,
             seconds: 0,
+            alarm: AlarmSettings::load(),
+            ringing: false,
+            rings: 0,
+            next_ring: Duration::from_secs(0),
+            finished: false,
         }
     }
 
     pub fn update(&mut self, elapsed: Duration, ctx: &egui::Context) {
-        if self.state != TimerState::Running {
-            return;
+        match self.state {
+            TimerState::Running => {
+                self.elapsed += elapsed;
+                if self.elapsed >= self.duration {
+                    self.state = TimerState::Completed;
+                    self.finished = true;
+                    self.ringing = self.alarm.repeat;
+                    self.rings = 0;
+                    self.ring();
+                    ctx.request_repaint();
+                }
+            }
+            TimerState::Completed => {
+                self.elapsed += elapsed; // overtime
+                if self.ringing && self.elapsed >= self.next_ring {
+                    self.ring();
+                }
+            }
+            TimerState::Snoozed => {
+                self.elapsed += elapsed;
+                if self.elapsed >= self.next_ring {
+                    self.state = TimerState::Completed;
+                    self.ringing = self.alarm.repeat;
+                    self.ring();
+                }
+            }
+            TimerState::Stopped | TimerState::Paused => {}
+        }
+    }
+
+    /// Returns true once when the countdown runs out.
+    pub fn take_finished(&mut self) -> bool {
+        std::mem::take(&mut self.finished)
+    }
+
+    /// Countdown or overtime for the window title, while the timer is in use.
+    pub fn title_status(&self) -> Option<String> {
+        match self.state {
+            TimerState::Stopped => None,
+            TimerState::Running | TimerState::Paused => Some(format!(
+                "{} · Timer",
+                Self::format_time(self.remaining_time())
+            )),
+            TimerState::Completed | TimerState::Snoozed => Some(format!(
+                "+{} · Time's up!",
+                Self::format_time(self.overtime())
+            )),
         }
+    }
+
+    fn ring(&mut self) {
+        let gain = if self.alarm.repeat && self.alarm.escalate {
+            (0.4 + 0.15 * self.rings as f32).min(1.0)
+        } else {
+            1.0
+        };
+        sound::play_at(SoundEvent::TimerFinished, gain);
+        self.rings += 1;
+        self.next_ring = self.elapsed + ALARM_INTERVAL;
+    }
+
+    fn snooze(&mut self) {
+        self.state = TimerState::Snoozed;
+        self.ringing = false;
+        self.next_ring = self.elapsed + Duration::from_secs(self.alarm.snooze_minutes as u64 * 60);
+    }
 
-        self.elapsed += elapsed;
+    /// How long ago the timer ran out.
+    fn overtime(&self) -> Duration {
+        self.elapsed.saturating_sub(self.duration)
+    }
 
-        if self.elapsed >= self.duration {
-            self.state = TimerState::Completed;
-            play_alarm();
-            ctx.request_repaint();
+    pub fn snapshot(&self) -> TimerSnapshot {
+        TimerSnapshot {
+            state: self.state,
+            elapsed: self.elapsed,
+            duration: self.duration,
+            next_ring: self.next_ring,
         }
     }
 
+    pub fn restore(&mut self, snapshot: TimerSnapshot) {
+        self.state = snapshot.state;
+        self.elapsed = snapshot.elapsed;
+        self.duration = snapshot.duration;
+        self.next_ring = snapshot.next_ring;
+        self.ringing = false; // undoing a reset brings the alarm back quietly
+    }
+
     fn remaining_time(&self) -> Duration {
         if self.elapsed > self.duration {
             Duration::from_secs(0)
@@ -72,7 +214,7 @@ impl Timer {
         if self.state == TimerState::Stopped || self.duration.as_secs_f32() == 0.0 {
             return 0.0;
         }
-        self.elapsed.as_secs_f32() / self.duration.as_secs_f32()
+        (self.elapsed.as_secs_f32() / self.duration.as_secs_f32()).min(1.0) // full once overtime
     }
 
     fn set_duration(&mut self) {
@@ -81,7 +223,7 @@ impl Timer {
         self.duration = Duration::from_secs(total_seconds);
     }
 
-    pub fn ui(&mut self, ui: &mut Ui) {
+    pub fn ui(&mut self, ui: &mut Ui, history: &mut History) {
         ui.vertical_centered(|ui| {
             ui.add_space(10.0);
             ui.heading("Custom Timer");
@@ -153,6 +295,33 @@ impl Timer {
                         }
                     });
 
+                    ui.add_space(10.0);
+
+                    let mut alarm_changed = false;
+                    ui.horizontal(|ui| {
+                        alarm_changed |= ui
+                            .checkbox(&mut self.alarm.repeat, "Repeat alarm until dismissed")
+                            .changed();
+                        if self.alarm.repeat {
+                            alarm_changed |= ui
+                                .checkbox(&mut self.alarm.escalate, "Get louder")
+                                .changed();
+                        }
+                    });
+                    ui.horizontal(|ui| {
+                        ui.label("Snooze for");
+                        alarm_changed |= ui
+                            .add(
+                                egui::DragValue::new(&mut self.alarm.snooze_minutes)
+                                    .range(1..=60)
+                                    .suffix(" min"),
+                            )
+                            .changed();
+                    });
+                    if alarm_changed {
+                        self.alarm.save();
+                    }
+
                     ui.add_space(20.0);
 
                     if ui.button("Start Timer").clicked() {
@@ -164,17 +333,27 @@ impl Timer {
                     }
                 }
 
-                TimerState::Running | TimerState::Paused | TimerState::Completed => {
+                TimerState::Running
+                | TimerState::Paused
+                | TimerState::Completed
+                | TimerState::Snoozed => {
                     let text = match self.state {
-                        TimerState::Completed => "Time's up!",
+                        TimerState::Completed | TimerState::Snoozed => "Time's up!",
                         _ => "Remaining Time",
                     };
 
                     ui.label(RichText::new(text).font(FontId::proportional(18.0)));
 
-                    let remaining = Self::format_time(self.remaining_time());
+                    let finished =
+                        matches!(self.state, TimerState::Completed | TimerState::Snoozed);
+                    let remaining = if finished {
+                        format!("+{}", Self::format_time(self.overtime()))
+                    } else {
+                        Self::format_time(self.remaining_time())
+                    };
 
-                    let timer_size = Vec2::splat(ui.available_width().min(240.0));
+                    let ring = typography::ring_size(ui);
+                    let timer_size = Vec2::splat(ring);
                     let (response, painter) = ui.allocate_painter(timer_size, egui::Sense::hover());
                     let center = response.rect.center();
                     let radius = response.rect.width() / 2.0 - 10.0;
@@ -182,13 +361,14 @@ impl Timer {
                     let progress = self.progress();
                     let angle = std::f32::consts::TAU * progress - std::f32::consts::FRAC_PI_2;
 
-                    painter.circle_stroke(center, radius, Stroke::new(5.0, Color32::DARK_GRAY));
+                    let palette = theme::palette();
+                    painter.circle_stroke(center, radius, Stroke::new(5.0, palette.dim));
 
                     let color = match self.state {
-                        TimerState::Completed => Color32::from_rgb(235, 87, 87), // Red
-                        TimerState::Paused => Color32::from_rgb(252, 186, 3),    // Amber
-                        TimerState::Running => Color32::from_rgb(79, 134, 198),  // Blue
-                        _ => Color32::GRAY,
+                        TimerState::Completed => palette.danger,
+                        TimerState::Paused | TimerState::Snoozed => palette.warning,
+                        TimerState::Running => palette.info,
+                        _ => palette.muted,
                     };
 
                     if self.state != TimerState::Stopped && progress > 0.0 {
@@ -222,14 +402,26 @@ impl Timer {
                         center,
                         Align2::CENTER_CENTER,
                         remaining,
-                        FontId::proportional(32.0),
-                        if self.state == TimerState::Completed {
-                            color
-                        } else {
-                            Color32::WHITE
-                        },
+                        typography::countdown_font(ring),
+                        if finished { color } else { palette.strong },
                     );
 
+                    if finished {
+                        ui.label(format!(
+                            "Finished {} ago",
+                            Self::format_time(self.overtime())
+                        ));
+                    }
+                    if self.state == TimerState::Snoozed {
+                        ui.label(
+                            RichText::new(format!(
+                                "Snoozed, rings again in {}",
+                                Self::format_time(self.next_ring.saturating_sub(self.elapsed))
+                            ))
+                            .weak(),
+                        );
+                    }
+
                     ui.add_space(30.0);
 
                     ui.horizontal(|ui| {
@@ -244,13 +436,33 @@ impl Timer {
                                     self.state = TimerState::Running;
                                 }
                             }
-                            TimerState::Completed => {}
-                            TimerState::Stopped => todo!(),
+                            TimerState::Completed => {
+                                if self.ringing && ui.button("Dismiss").clicked() {
+                                    self.ringing = false;
+                                }
+                                if ui
+                                    .button(format!("Snooze {} min", self.alarm.snooze_minutes))
+                                    .clicked()
+                                {
+                                    self.snooze();
+                                }
+                            }
+                            TimerState::Snoozed => {
+                                if ui.button("Dismiss").clicked() {
+                                    self.state = TimerState::Completed;
+                                }
+                            }
+                            TimerState::Stopped => {}
                         }
 
                         if ui.button("Reset").clicked() {
+                            history.record_destructive(
+                                "Reset timer",
+                                Snapshot::Timer(self.snapshot()),
+                            );
                             self.state = TimerState::Stopped;
                             self.elapsed = Duration::from_secs(0);
+                            self.ringing = false;
                         }
                     });
                 }
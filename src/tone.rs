@@ -0,0 +1,338 @@
+// Synthesized chimes described as data, e.g. in ~/.rust_pomodoro_backup/tones.json:
+//
+//   { "name": "Doorbell", "wave": "triangle", "volume": 0.3, "repeat": 2,
+//     "envelope": { "attack_ms": 5, "release_ms": 80 },
+//     "steps": ["E5 200", "C5 400", "rest 150", "660 100 square"] }
+//
+// A step is a pitch (note name like `C4`/`F#5`/`Bb3`, or a frequency in Hz) or `rest`,
+// then a length in milliseconds, then optionally a waveform for that step only.
+use rodio::buffer::SamplesBuffer;
+use serde::{Deserialize, Serialize};
+use std::{f32::consts::TAU, fs, path::Path};
+
+const SAMPLE_RATE: u32 = 44_100;
+
+#[derive(Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Waveform {
+    #[default]
+    Sine,
+    Square,
+    Triangle,
+    Sawtooth,
+}
+
+impl Waveform {
+    fn parse(text: &str) -> Option<Self> {
+        match text.to_lowercase().as_str() {
+            "sine" => Some(Waveform::Sine),
+            "square" => Some(Waveform::Square),
+            "triangle" => Some(Waveform::Triangle),
+            "sawtooth" | "saw" => Some(Waveform::Sawtooth),
+            _ => None,
+        }
+    }
+
+    /// Sample at `phase`, a position within one cycle from 0 to 1.
+    fn sample(&self, phase: f32) -> f32 {
+        match self {
+            Waveform::Sine => (TAU * phase).sin(),
+            Waveform::Square => {
+                if phase < 0.5 {
+                    1.0
+                } else {
+                    -1.0
+                }
+            }
+            Waveform::Triangle => 1.0 - 4.0 * (phase - 0.5).abs(),
+            Waveform::Sawtooth => 2.0 * phase - 1.0,
+        }
+    }
+}
+
+/// Fade in and out of every note, which also keeps notes from clicking.
+#[derive(Clone, Copy, Serialize, Deserialize)]
+pub struct Envelope {
+    pub attack_ms: u32,
+    pub release_ms: u32,
+}
+
+impl Default for Envelope {
+    fn default() -> Self {
+        Self {
+            attack_ms: 5,
+            release_ms: 20,
+        }
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct ToneSequence {
+    pub name: String,
+    #[serde(default)]
+    pub wave: Waveform,
+    #[serde(default = "default_volume")]
+    pub volume: f32,
+    #[serde(default)]
+    pub envelope: Envelope,
+    #[serde(default = "default_repeat")]
+    pub repeat: u32,
+    pub steps: Vec<String>,
+}
+
+fn default_volume() -> f32 {
+    0.25
+}
+
+fn default_repeat() -> u32 {
+    1
+}
+
+struct Step {
+    frequency: Option<f32>, // None is a rest
+    millis: u32,
+    wave: Option<Waveform>,
+}
+
+impl ToneSequence {
+    fn new(name: &str, volume: f32, repeat: u32, steps: &[&str]) -> Self {
+        Self {
+            name: name.to_string(),
+            wave: Waveform::Sine,
+            volume,
+            envelope: Envelope::default(),
+            repeat,
+            steps: steps.iter().map(|step| step.to_string()).collect(),
+        }
+    }
+
+    fn parse_steps(&self) -> Result<Vec<Step>, String> {
+        self.steps
+            .iter()
+            .map(|step| {
+                parse_step(step)
+                    .ok_or_else(|| format!("Tone \"{}\": `{}` is not a step", self.name, step))
+            })
+            .collect()
+    }
+
+    /// The sequence as something a sink can play, or None if a step cannot be parsed.
+    pub fn source(&self) -> Option<SamplesBuffer<f32>> {
+        let samples = self.render().ok()?;
+        Some(SamplesBuffer::new(1, SAMPLE_RATE, samples))
+    }
+
+    /// Synthesizes the whole sequence as mono samples at `SAMPLE_RATE`.
+    fn render(&self) -> Result<Vec<f32>, String> {
+        let steps = self.parse_steps()?;
+        let mut samples = Vec::new();
+        for _ in 0..self.repeat.clamp(1, 20) {
+            for step in &steps {
+                let count = sample_count(step.millis);
+                let Some(frequency) = step.frequency else {
+                    samples.extend(std::iter::repeat(0.0).take(count));
+                    continue;
+                };
+                let wave = step.wave.unwrap_or(self.wave);
+                let attack = sample_count(self.envelope.attack_ms).max(1);
+                let release = sample_count(self.envelope.release_ms).max(1);
+                for i in 0..count {
+                    let phase = (frequency as f64 * i as f64 / SAMPLE_RATE as f64).fract() as f32;
+                    let gain = (i as f32 / attack as f32)
+                        .min((count - i) as f32 / release as f32)
+                        .min(1.0);
+                    samples.push(wave.sample(phase) * gain * self.volume);
+                }
+            }
+        }
+        Ok(samples)
+    }
+}
+
+/// Number of samples in `millis` milliseconds, widened so long envelopes cannot overflow.
+fn sample_count(millis: u32) -> usize {
+    (SAMPLE_RATE as u64 * millis as u64 / 1000) as usize
+}
+
+fn parse_step(text: &str) -> Option<Step> {
+    let mut parts = text.split_whitespace();
+    let pitch = parts.next()?;
+    let millis = parts.next()?.trim_end_matches("ms").parse::<u32>().ok()?;
+    let wave = match parts.next() {
+        Some(wave) => Some(Waveform::parse(wave)?),
+        None => None,
+    };
+    if parts.next().is_some() || millis == 0 || millis > 10_000 {
+        return None;
+    }
+
+    let frequency = if pitch.eq_ignore_ascii_case("rest") {
+        None
+    } else if let Ok(hz) = pitch.parse::<f32>() {
+        if !(20.0..=20_000.0).contains(&hz) {
+            return None;
+        }
+        Some(hz)
+    } else {
+        Some(parse_note(pitch)?)
+    };
+    Some(Step {
+        frequency,
+        millis,
+        wave,
+    })
+}
+
+/// `A4` is 440 Hz; `C#5`, `Db5` and friends work as expected.
+fn parse_note(text: &str) -> Option<f32> {
+    let mut chars = text.chars();
+    let semitone = match chars.next()?.to_ascii_uppercase() {
+        'C' => 0,
+        'D' => 2,
+        'E' => 4,
+        'F' => 5,
+        'G' => 7,
+        'A' => 9,
+        'B' => 11,
+        _ => return None,
+    };
+    let rest = chars.as_str();
+    let (accidental, octave) = if let Some(octave) = rest.strip_prefix('#') {
+        (1, octave)
+    } else if let Some(octave) = rest.strip_prefix('b') {
+        (-1, octave)
+    } else {
+        (0, rest)
+    };
+    let octave = octave.parse::<i32>().ok().filter(|o| (0..=8).contains(o))?;
+    let midi = 12 * (octave + 1) + semitone + accidental;
+    Some(440.0 * 2f32.powf((midi - 69) as f32 / 12.0))
+}
+
+/// The tones the app ships with. The notification and alarm match the original sounds.
+pub fn presets() -> Vec<ToneSequence> {
+    vec![
+        ToneSequence::new("Notification", 0.20, 1, &["440 250"]),
+        ToneSequence::new("Alarm", 0.25, 2, &["880 300", "660 300"]),
+        ToneSequence::new("Reminder", 0.20, 1, &["C5 150", "E5 150", "G5 150"]),
+    ]
+}
+
+/// Built-in presets followed by the user's tones from tones.json, which replace presets
+/// of the same name. Also returns a message for every definition that cannot be played.
+pub fn load() -> (Vec<ToneSequence>, Vec<String>) {
+    let mut tones = presets();
+    let mut errors = Vec::new();
+
+    if let Some(home_dir) = dirs::home_dir() {
+        let backup_dir = home_dir.join(".rust_pomodoro_backup");
+        let file_path = backup_dir.join("tones.json");
+        if !Path::new(&file_path).exists() {
+            // Seed the file with the presets so there is something to copy from
+            fs::create_dir_all(&backup_dir).unwrap_or(());
+            let presets_json = serde_json::to_string_pretty(&tones).unwrap_or_default();
+            fs::write(&file_path, presets_json).unwrap_or(());
+        } else if let Ok(contents) = fs::read_to_string(&file_path) {
+            match serde_json::from_str::<Vec<ToneSequence>>(&contents) {
+                Ok(user_tones) => {
+                    for tone in user_tones {
+                        if let Err(error) = tone.parse_steps() {
+                            errors.push(error);
+                            continue;
+                        }
+                        tones.retain(|other| other.name != tone.name);
+                        tones.push(tone);
+                    }
+                }
+                Err(error) => errors.push(format!("tones.json: {}", error)),
+            }
+        }
+    }
+
+    (tones, errors)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn close(a: f32, b: f32) -> bool {
+        (a - b).abs() < 0.01
+    }
+
+    #[test]
+    fn notes_map_to_equal_tempered_frequencies() {
+        assert!(close(parse_note("A4").unwrap(), 440.0));
+        assert!(close(parse_note("a5").unwrap(), 880.0));
+        assert!(close(parse_note("C4").unwrap(), 261.63));
+        assert!(close(
+            parse_note("C#5").unwrap(),
+            parse_note("Db5").unwrap()
+        ));
+        assert!(close(parse_note("Bb3").unwrap(), 233.08));
+    }
+
+    #[test]
+    fn rejects_bad_notes() {
+        for text in ["", "H4", "A", "A9", "A-1", "A#", "Ax4", "C##4"] {
+            assert!(parse_note(text).is_none(), "{}", text);
+        }
+    }
+
+    #[test]
+    fn parses_notes_frequencies_and_rests() {
+        let step = parse_step("E5 200").unwrap();
+        assert!(close(step.frequency.unwrap(), 659.26));
+        assert_eq!(step.millis, 200);
+        assert!(step.wave.is_none());
+
+        let step = parse_step("660 100ms square").unwrap();
+        assert_eq!(step.frequency, Some(660.0));
+        assert_eq!(step.millis, 100);
+        assert!(step.wave == Some(Waveform::Square));
+
+        let step = parse_step("REST 150").unwrap();
+        assert!(step.frequency.is_none());
+    }
+
+    #[test]
+    fn rejects_bad_steps() {
+        for text in [
+            "",
+            "440",
+            "440 0",
+            "440 10001",
+            "440 -5",
+            "10 100",
+            "25000 100",
+            "A4 100 noise",
+            "A4 100 sine loud",
+            "rest soon",
+        ] {
+            assert!(parse_step(text).is_none(), "{}", text);
+        }
+    }
+
+    #[test]
+    fn long_envelopes_do_not_overflow() {
+        let mut tone = ToneSequence::new("Swell", 0.2, 1, &["A4 10"]);
+        tone.envelope = Envelope {
+            attack_ms: u32::MAX,
+            release_ms: 200_000,
+        };
+        let samples = tone.render().unwrap();
+        assert_eq!(samples.len(), 441);
+        assert!(samples.iter().all(|sample| sample.abs() <= 0.2));
+    }
+
+    #[test]
+    fn a_bad_step_names_the_tone() {
+        let tone = ToneSequence::new("Broken", 0.2, 1, &["A4 100", "Z9 100"]);
+        let error = tone.render().unwrap_err();
+        assert!(
+            error.contains("Broken") && error.contains("Z9 100"),
+            "{}",
+            error
+        );
+    }
+}
@@ -0,0 +1,209 @@
+use egui::{FontData, FontDefinitions, FontFamily, FontId, RichText};
+use serde::{Deserialize, Serialize};
+use std::{fs, path::Path, sync::Arc, sync::RwLock};
+
+use crate::theme;
+
+const CUSTOM_FONT: &str = "custom";
+const COUNTDOWN_FAMILY: &str = "countdown";
+
+// The countdown text relative to its ring: 32px in the original 240px ring
+const COUNTDOWN_RATIO: f32 = 32.0 / 240.0;
+
+static COUNTDOWN_SCALE: RwLock<f32> = RwLock::new(1.0);
+
+/// Side of a progress ring or clock face that fills `ui` without crowding out what sits
+/// below it.
+pub fn ring_size(ui: &egui::Ui) -> f32 {
+    let height = (ui.available_height() * 0.6).max(160.0);
+    ui.available_width().min(height)
+}
+
+/// The font for a countdown drawn inside a ring `ring_size` across.
+pub fn countdown_font(ring_size: f32) -> FontId {
+    let scale = COUNTDOWN_SCALE.read().map(|scale| *scale).unwrap_or(1.0);
+    FontId::new(
+        ring_size * COUNTDOWN_RATIO * scale,
+        FontFamily::Name(COUNTDOWN_FAMILY.into()),
+    )
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct Typography {
+    pub ui_scale: f32,
+    pub font_file: String, // a TTF/OTF file for all text, empty for egui's font
+    pub monospace_digits: bool,
+    pub countdown_scale: f32,
+    #[serde(skip)]
+    font_input: String, // font path being typed, kept in `font_file` once it loads
+    #[serde(skip)]
+    font_problem: Option<String>,
+}
+
+impl Typography {
+    pub fn new() -> Self {
+        Self {
+            ui_scale: 1.0,
+            font_file: String::new(),
+            monospace_digits: true,
+            countdown_scale: 1.0,
+            font_input: String::new(),
+            font_problem: None,
+        }
+    }
+
+    /// Sets the zoom, the fonts and the countdown size.
+    pub fn apply(&mut self, ctx: &egui::Context) {
+        ctx.set_zoom_factor(self.ui_scale);
+        self.apply_countdown_scale();
+
+        let mut fonts = FontDefinitions::default();
+        if !self.font_file.trim().is_empty() {
+            match load_font(self.font_file.trim()) {
+                Ok(data) => {
+                    fonts.font_data.insert(
+                        CUSTOM_FONT.to_string(),
+                        Arc::new(FontData::from_owned(data)),
+                    );
+                    if let Some(family) = fonts.families.get_mut(&FontFamily::Proportional) {
+                        family.insert(0, CUSTOM_FONT.to_string()); // the defaults stay as fallbacks
+                    }
+                }
+                Err(problem) => {
+                    self.font_problem = Some(format!("{}, using the built-in font", problem))
+                }
+            }
+        }
+
+        // Monospace digits keep the countdown from shifting sideways as it ticks
+        let countdown = if self.monospace_digits {
+            FontFamily::Monospace
+        } else {
+            FontFamily::Proportional
+        };
+        let countdown_fonts = fonts.families.get(&countdown).cloned().unwrap_or_default();
+        fonts
+            .families
+            .insert(FontFamily::Name(COUNTDOWN_FAMILY.into()), countdown_fonts);
+        ctx.set_fonts(fonts);
+    }
+
+    /// Only the countdown size, which is read while painting and needs no font rebuild.
+    fn apply_countdown_scale(&self) {
+        if let Ok(mut scale) = COUNTDOWN_SCALE.write() {
+            *scale = self.countdown_scale;
+        }
+    }
+
+    pub fn settings_ui(&mut self, ui: &mut egui::Ui) {
+        ui.heading("Text and scaling");
+        ui.add_space(5.0);
+        let mut changed = false;
+        ui.horizontal(|ui| {
+            ui.label("Interface scale:");
+            let response = ui.add(
+                egui::Slider::new(&mut self.ui_scale, 0.75..=2.5)
+                    .step_by(0.05)
+                    .custom_formatter(|scale, _| format!("{:.0}%", scale * 100.0)),
+            );
+            // Rescaling mid-drag would move the slider out from under the pointer
+            changed |= response.drag_stopped() || (response.changed() && !response.dragged());
+        });
+        ui.horizontal(|ui| {
+            ui.label("Countdown size:");
+            let response = ui.add(
+                egui::Slider::new(&mut self.countdown_scale, 0.5..=2.0)
+                    .step_by(0.05)
+                    .custom_formatter(|scale, _| format!("{:.0}%", scale * 100.0)),
+            );
+            // Resized live while dragging, saved once the drag ends
+            if response.changed() {
+                self.apply_countdown_scale();
+            }
+            if response.drag_stopped() || (response.changed() && !response.dragged()) {
+                self.save();
+            }
+        });
+        ui.horizontal(|ui| {
+            ui.label("Font:");
+            let response = ui.add(
+                egui::TextEdit::singleline(&mut self.font_input)
+                    .hint_text("Built-in, or a TTF/OTF path")
+                    .desired_width(260.0),
+            );
+            // Only a font that loads is kept, so a bad path never reaches the saved settings
+            let path = self.font_input.trim().to_string();
+            if response.lost_focus() && path != self.font_file {
+                let loaded = if path.is_empty() {
+                    Ok(Vec::new())
+                } else {
+                    load_font(&path)
+                };
+                match loaded {
+                    Ok(_) => {
+                        self.font_file = path;
+                        self.font_problem = None;
+                        changed = true;
+                    }
+                    Err(problem) => {
+                        self.font_problem = Some(format!("{}, the font was not changed", problem))
+                    }
+                }
+            }
+        });
+        if let Some(problem) = &self.font_problem {
+            ui.label(
+                RichText::new(format!("⚠ {}", problem))
+                    .small()
+                    .color(theme::palette().warning),
+            );
+        }
+        changed |= ui
+            .checkbox(&mut self.monospace_digits, "Monospace digits in countdowns")
+            .changed();
+        ui.label(RichText::new("The timer rings grow with the window").weak());
+
+        if changed {
+            self.apply(ui.ctx());
+            self.save();
+        }
+    }
+
+    pub fn save(&self) {
+        if let Some(home_dir) = dirs::home_dir() {
+            let backup_dir = home_dir.join(".rust_pomodoro_backup");
+            fs::create_dir_all(&backup_dir).unwrap_or(());
+            let typography_json = serde_json::to_string(&self).unwrap_or_default();
+            fs::write(backup_dir.join("typography.json"), typography_json).unwrap_or(());
+        }
+    }
+
+    pub fn load() -> Self {
+        if let Some(home_dir) = dirs::home_dir() {
+            let file_path = home_dir.join(".rust_pomodoro_backup/typography.json");
+            if Path::new(&file_path).exists() {
+                if let Ok(contents) = fs::read_to_string(file_path) {
+                    if let Ok(typography) = serde_json::from_str::<Typography>(&contents) {
+                        // Keep a hand-edited file within the ranges the sliders offer
+                        return Self {
+                            ui_scale: typography.ui_scale.clamp(0.75, 2.5),
+                            countdown_scale: typography.countdown_scale.clamp(0.5, 2.0),
+                            font_input: typography.font_file.clone(),
+                            ..typography
+                        };
+                    }
+                }
+            }
+        }
+        Self::new()
+    }
+}
+
+/// Reads a font file and parses it the way egui will, since egui panics on a font it
+/// cannot read.
+fn load_font(path: &str) -> Result<Vec<u8>, String> {
+    let data = fs::read(path).map_err(|_| "Font file not found".to_string())?;
+    ab_glyph::FontRef::try_from_slice(&data)
+        .map_err(|_| "Not a TTF or OTF font that can be read".to_string())?;
+    Ok(data)
+}